@@ -0,0 +1,32 @@
+use bevy::{app::AppExit, prelude::*};
+use bevy_dialogue_system::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins((DialoguePlugin, DefaultPlugins))
+        .add_systems(Startup, setup)
+        .add_systems(Update, exit)
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2dBundle::default());
+
+    commands
+        .spawn(TextBundle::default())
+        .insert(DialogueSource::new(
+            asset_server.load("example.dialogue.ron"),
+        ));
+}
+
+fn exit(
+    mut app_exit: EventWriter<AppExit>,
+    dialogue: Query<Option<&Dialogue>, With<DialogueSource>>,
+) {
+    if dialogue
+        .iter()
+        .all(|dialogue| dialogue.is_some_and(|dialogue| dialogue.is_finished()))
+    {
+        app_exit.send_default();
+    }
+}
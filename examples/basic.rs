@@ -44,19 +44,18 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
         .insert(Dialogue::new().with_paragraphs(
             vec![
                 Paragraph::new().with_sentences(vec![Sentence::new().with_text(
-                    "Press Enter, Space or A to advance",
+                    "Press Enter, Space or A, click or tap to advance",
                 )]),
                 Paragraph::new().with_sentences(vec![
-                    Sentence::new().with_text("This is a very basic story,\n"),
-                    Sentence::new().with_text("that uses colors,\n")
-                        .with_color(Color::RED),
-                    Sentence::new().with_text("different text sizes,\n").with_font_size(64.),
+                    Sentence::new().with_markup(
+                        "This is a very basic story,\nthat uses [color=red]colors[/color], [wave]effects[/wave],\n[size=64]different text sizes[/size],\n",
+                    ),
                     Sentence::new().with_text(
                         "different fonts for accessibilitys sake,\n",
                     ).with_font(dyslexic_font),
                     Sentence::new().with_text("and typewriter text... "),
-                    Sentence::new().with_text(
-                        "that you can skip if you're really impatient.",
+                    Sentence::new().with_markup(
+                        "that you can skip{wait=0.5} if you're {speed=3}really impatient.",
                     )
                     .create_typewriter(),
                 ]),
@@ -80,6 +79,25 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                     .with_action(change_background),
                     Sentence::new().with_text("like so!"),
                 ]),
+                Paragraph::new().with_sentences(vec![
+                    Sentence::new().with_text(
+                        "It can even let you choose where the story goes.",
+                    )
+                    .with_condition(DialogueCondition::variable("asked").not()),
+                    Sentence::new().with_text("And it remembers what you picked, anything else?")
+                        .with_condition(DialogueCondition::variable("asked")),
+                ])
+                .with_name("choice")
+                .with_choice(Choice::new().with_options(vec![
+                    ChoiceOption::new("Tell me more").with_paragraphs(vec![
+                        Paragraph::new().with_sentences(vec![Sentence::new().with_text(
+                            "Use the arrow keys, a gamepad or the mouse to pick an option.",
+                        )])
+                        .push_variable_change(VariableChange::set("asked", true)),
+                    ])
+                    .with_jump("choice"),
+                    ChoiceOption::new("I've seen enough"),
+                ])),
                 Paragraph::new().with_sentences(vec![Sentence::new().with_text(
                     "And it was only after I made this that I realised the yarnspinner plugin was released not ten days ago...",
                 ).with_typewriter(TypeWriter::new().with_speed(0.7))])
@@ -101,7 +119,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
 }
 
 fn exit(mut app_exit: EventWriter<AppExit>, dialogue: Query<&Dialogue>) {
-    if dialogue.iter().all(|dialogue| dialogue.is_empty()) {
+    if dialogue.iter().all(|dialogue| dialogue.is_finished()) {
         app_exit.send_default();
     }
 }
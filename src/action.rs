@@ -0,0 +1,211 @@
+use crate::prelude::*;
+use bevy::ecs::system::SystemId;
+use std::sync::Arc;
+
+/// A closure run as a [`SentenceAction`], given the entity of the dialogue
+pub type ActionClosure = Arc<dyn Fn(Entity, &mut World) + Send + Sync>;
+
+/// Something a sentence does to the world, run from [`DialogueActions`] once the sentence is entered or left
+///
+/// Registered systems are only initialized once, so unlike plain functions they keep their state and can use any
+/// system params, register them with [`World::register_system`]
+/// ```
+/// # use bevy_dialogue_system::prelude::*;
+/// # use bevy::prelude::*;
+/// #[derive(Resource, Default)]
+/// struct Inventory(Vec<String>);
+///
+/// fn give_item(In(item): In<String>, mut inventory: ResMut<Inventory>) {
+///     inventory.0.push(item);
+/// }
+///
+/// let mut app = App::new();
+/// app.add_plugins((MinimalPlugins, AssetPlugin::default(), bevy::input::InputPlugin, DialoguePlugin))
+///     .init_resource::<Inventory>();
+/// # app.finish();
+/// # app.cleanup();
+/// let give_item = app.world.register_system(give_item);
+///
+/// let dialogue = app
+///     .world
+///     .spawn(Dialogue::new().with_paragraphs(vec![Paragraph::new().with_sentences(vec![
+///         Sentence::new()
+///             .with_text("Take this sword")
+///             .with_sentence_action(SentenceAction::with_argument(give_item, "sword")),
+///         Sentence::new()
+///             .with_text("And this shield")
+///             .with_sentence_action(SentenceAction::closure(|_, world| {
+///                 world.resource_mut::<Inventory>().0.push("shield".to_string());
+///             })),
+///     ])]))
+///     .id();
+/// app.update();
+///
+/// for _ in 0..4 {
+///     app.world.send_event(DialogueInput::to(dialogue, DialogueAction::Advance));
+///     app.update();
+/// }
+/// assert_eq!(app.world.resource::<Inventory>().0, vec!["sword", "shield"]);
+/// ```
+#[derive(Clone)]
+pub enum SentenceAction {
+    World(fn(&mut World)),
+    /// Given the entity of the dialogue the sentence belongs to
+    WithDialogue(fn(In<Entity>, &mut World)),
+    System(SystemId),
+    /// A registered system given the entity of the dialogue the sentence belongs to
+    SystemWithDialogue(SystemId<Entity>),
+    /// A registered system given an argument from the sentence, so that one system can serve many lines
+    SystemWithArgument(SystemId<String>, String),
+    /// A closure given the entity of the dialogue, which can capture whatever the line needs
+    Closure(ActionClosure),
+    /// A command of the [`DialogueCommands`] resource, usually from a dialogue file
+    Command(DialogueCommand),
+}
+impl SentenceAction {
+    pub fn system(system: SystemId) -> Self {
+        Self::System(system)
+    }
+    pub fn with_dialogue(system: SystemId<Entity>) -> Self {
+        Self::SystemWithDialogue(system)
+    }
+    pub fn with_argument(system: SystemId<String>, argument: impl ToString) -> Self {
+        Self::SystemWithArgument(system, argument.to_string())
+    }
+    pub fn closure(action: impl Fn(Entity, &mut World) + Send + Sync + 'static) -> Self {
+        Self::Closure(Arc::new(action))
+    }
+
+    pub fn run(&self, dialogue: Entity, world: &mut World) {
+        let result = match self {
+            Self::World(action) => {
+                world.run_system_once(*action);
+                Ok(())
+            }
+            Self::WithDialogue(action) => {
+                world.run_system_once_with(dialogue, *action);
+                Ok(())
+            }
+            Self::System(system) => world.run_system(*system).map_err(|error| error.to_string()),
+            Self::SystemWithDialogue(system) => world
+                .run_system_with_input(*system, dialogue)
+                .map_err(|error| error.to_string()),
+            Self::SystemWithArgument(system, argument) => world
+                .run_system_with_input(*system, argument.clone())
+                .map_err(|error| error.to_string()),
+            Self::Closure(action) => {
+                action(dialogue, world);
+                Ok(())
+            }
+            Self::Command(command) => match world.get_resource::<DialogueCommands>().cloned() {
+                Some(commands) => commands.run(command, dialogue, world),
+                None => Err(format!("unknown command {}", command)),
+            },
+        };
+        if let Err(error) = result {
+            warn!("Could not run the action of a sentence in {dialogue:?}: {error}");
+        }
+    }
+}
+impl std::fmt::Debug for SentenceAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::World(action) => f.debug_tuple("World").field(action).finish(),
+            Self::WithDialogue(action) => f.debug_tuple("WithDialogue").field(action).finish(),
+            Self::System(system) => f.debug_tuple("System").field(system).finish(),
+            Self::SystemWithDialogue(system) => {
+                f.debug_tuple("SystemWithDialogue").field(system).finish()
+            }
+            Self::SystemWithArgument(system, argument) => f
+                .debug_tuple("SystemWithArgument")
+                .field(system)
+                .field(argument)
+                .finish(),
+            Self::Closure(_) => f.write_str("Closure(..)"),
+            Self::Command(command) => f.debug_tuple("Command").field(command).finish(),
+        }
+    }
+}
+
+/// When a sentence's action runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum ActionTiming {
+    /// Once the sentence starts being shown
+    Enter,
+    /// Once the dialogue moves on from the sentence, after it has been shown in full
+    #[default]
+    Exit,
+}
+
+/// Every action triggered by any dialogue, run in order by the plugin
+/// ```
+/// # use bevy_dialogue_system::prelude::*;
+/// # use bevy::prelude::*;
+/// #[derive(Resource, Default)]
+/// struct Greeted(Vec<Entity>);
+///
+/// fn greet(In(dialogue): In<Entity>, world: &mut World) {
+///     world.resource_mut::<Greeted>().0.push(dialogue);
+/// }
+///
+/// let mut app = App::new();
+/// app.add_plugins((MinimalPlugins, AssetPlugin::default(), bevy::input::InputPlugin, DialoguePlugin))
+///     .init_resource::<Greeted>();
+/// # app.finish();
+/// # app.cleanup();
+///
+/// let dialogue = || {
+///     Dialogue::new().with_paragraphs(vec![Paragraph::new().with_sentences(vec![Sentence::new()
+///         .with_text("Hello")
+///         .with_dialogue_action(greet)
+///         .with_action_timing(ActionTiming::Enter)])])
+/// };
+/// let first = app.world.spawn(dialogue()).id();
+/// let second = app.world.spawn(dialogue()).id();
+///
+/// app.update();
+///
+/// let greeted = &app.world.resource::<Greeted>().0;
+/// assert!(greeted.contains(&first) && greeted.contains(&second));
+/// ```
+#[derive(Resource, Debug, Default)]
+pub struct DialogueActions(VecDeque<(Entity, SentenceAction, bool)>);
+
+impl DialogueActions {
+    /// Queues an action to run as if it came from the dialogue
+    pub fn push(&mut self, dialogue: Entity, action: SentenceAction) {
+        self.0.push_back((dialogue, action, false));
+    }
+    /// Queues an action that the dialogue waits on, putting [`BlockingAction`] on it just before the action runs
+    pub fn push_blocking(&mut self, dialogue: Entity, action: SentenceAction) {
+        self.0.push_back((dialogue, action, true));
+    }
+    pub fn pop(&mut self) -> Option<(Entity, SentenceAction)> {
+        self.next().map(|(dialogue, action, _)| (dialogue, action))
+    }
+    /// The next action along with whether it blocks
+    pub(crate) fn next(&mut self) -> Option<(Entity, SentenceAction, bool)> {
+        self.0.pop_front()
+    }
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Put on a dialogue while it waits for a blocking action, see [`Sentence::with_blocking_action`]
+///
+/// Removing it lets the dialogue go on, just like sending [`ResumeDialogue`]
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct BlockingAction;
+
+/// What a dialogue does once it stops waiting for a blocking action
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum AfterWait {
+    /// The action ran as the sentence was entered, so the sentence is simply shown
+    Stay,
+    Advance,
+    Choose(usize),
+}
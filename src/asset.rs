@@ -0,0 +1,537 @@
+use crate::prelude::*;
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    utils::BoxedFuture,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The paragraphs of a dialogue written out in a `.dialogue.ron` or `.dialogue.json` file
+///
+/// Use [`DialogueSource`] to fill a [`Dialogue`] with them once they have loaded
+#[derive(Asset, TypePath, Debug, Clone, Default)]
+pub struct DialogueAsset {
+    paragraphs: Vec<Paragraph>,
+}
+impl DialogueAsset {
+    pub fn new(paragraphs: Vec<Paragraph>) -> Self {
+        Self { paragraphs }
+    }
+    pub fn paragraphs(&self) -> &Vec<Paragraph> {
+        &self.paragraphs
+    }
+}
+
+/// Fills the [`Dialogue`] on the same entity with the paragraphs of a [`DialogueAsset`] once it has loaded,
+/// adding the [`Dialogue`] if there isn't one
+///
+/// When the asset changes on disk the new paragraphs are swapped in with [`Dialogue::reload_paragraphs`],
+/// this needs bevy's `file_watcher` feature
+#[derive(Component, Debug, Clone)]
+pub struct DialogueSource {
+    handle: Handle<DialogueAsset>,
+    loaded: bool,
+}
+impl DialogueSource {
+    pub fn new(handle: Handle<DialogueAsset>) -> Self {
+        Self {
+            handle,
+            loaded: false,
+        }
+    }
+    pub fn handle(&self) -> &Handle<DialogueAsset> {
+        &self.handle
+    }
+    pub fn is_loaded(&self) -> bool {
+        self.loaded
+    }
+    pub(crate) fn mark_loaded(&mut self) {
+        self.loaded = true;
+    }
+}
+
+/// The layout of a dialogue file
+///
+/// ```
+/// # use bevy_dialogue_system::prelude::*;
+/// # use bevy::prelude::*;
+/// let descriptor: DialogueDescriptor = ron::from_str(r##"(
+///     paragraphs: [
+///         (
+///             name: Some("start"),
+///             width: Some(Percent(50.)),
+///             sentences: [
+///                 (text: "Hello ", color: Some("#ff0000")),
+///                 (text: "there", font_size: Some(64.), typewriter: Some((speed: Some(0.7)))),
+///             ],
+///         ),
+///         (
+///             condition: Some("gold >= 10"),
+///             sentences: [(text: "You look rich", set: {"noticed": true})],
+///         ),
+///     ],
+/// )"##).unwrap();
+///
+/// let paragraphs = descriptor.into_paragraphs(|_| Handle::default()).unwrap();
+///
+/// assert_eq!(paragraphs.len(), 2);
+/// assert_eq!(paragraphs[0].name(), Some("start"));
+/// assert_eq!(paragraphs[0].sentences()[0].color(), &Color::RED);
+/// assert_eq!(paragraphs[0].sentences()[1].typewriter().speed_override(), Some(0.7));
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct DialogueDescriptor {
+    pub paragraphs: Vec<ParagraphDescriptor>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct ParagraphDescriptor {
+    pub name: Option<String>,
+    pub jump: Option<String>,
+    pub stop: bool,
+    pub condition: Option<String>,
+    pub set: BTreeMap<String, DialogueValue>,
+    pub add: BTreeMap<String, DialogueValue>,
+    /// Calls to [`DialogueCommands`], such as `"give_item sword 1"`
+    pub commands: Vec<String>,
+    pub sentences: Vec<SentenceDescriptor>,
+    pub position: Option<UiRect>,
+    pub width: Option<Val>,
+    pub choice: Option<ChoiceDescriptor>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct SentenceDescriptor {
+    pub text: String,
+    pub speaker: Option<String>,
+    /// What the line is remembered as once seen, see [`Sentence::with_line_id`]
+    pub line_id: Option<String>,
+    /// Used instead of `text`, see [`Sentence::with_markup`]
+    pub markup: Option<String>,
+    pub color: Option<ColorDescriptor>,
+    /// The path of the font, relative to the assets folder
+    pub font: Option<String>,
+    /// The path of the font used for `[b]` markup
+    pub bold_font: Option<String>,
+    pub font_size: Option<f32>,
+    pub effects: Vec<TextEffect>,
+    pub typewriter: Option<TypeWriterDescriptor>,
+    pub jump: Option<String>,
+    pub condition: Option<String>,
+    pub set: BTreeMap<String, DialogueValue>,
+    pub add: BTreeMap<String, DialogueValue>,
+    /// A call to [`DialogueCommands`] run as the sentence's action
+    pub command: Option<String>,
+    pub action_timing: Option<ActionTiming>,
+    /// Whether the dialogue waits for the command until it is resumed, see [`Sentence::with_blocking_action`]
+    pub blocking: bool,
+    /// A pause once the sentence has been typed out, such as `Some(Seconds(1.5))`
+    pub wait: Option<DialogueWait>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct TypeWriterDescriptor {
+    pub speed: Option<f32>,
+    pub mode: Option<TypeWriterMode>,
+    pub reveal: Option<TypeWriterReveal>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct ChoiceDescriptor {
+    pub options: Vec<ChoiceOptionDescriptor>,
+    pub highlight: Option<ColorDescriptor>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct ChoiceOptionDescriptor {
+    pub label: SentenceDescriptor,
+    pub paragraphs: Vec<ParagraphDescriptor>,
+    pub jump: Option<String>,
+    pub condition: Option<String>,
+}
+
+/// Either a hex code such as `"#ff8800"` or a full [`Color`]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum ColorDescriptor {
+    Hex(String),
+    Color(Color),
+}
+
+impl DialogueDescriptor {
+    /// Builds the paragraphs described, using `load_font` to turn font paths into handles
+    ///
+    /// Any command is reported as unknown, use [`DialogueDescriptor::into_paragraphs_with`] for dialogue that calls them
+    pub fn into_paragraphs(
+        self,
+        load_font: impl FnMut(&str) -> Handle<Font>,
+    ) -> Result<Vec<Paragraph>, DialogueAssetError> {
+        self.into_paragraphs_with(&DialogueCommands::default(), load_font)
+    }
+    /// Builds the paragraphs described, checking their calls against `commands`
+    pub fn into_paragraphs_with(
+        self,
+        commands: &DialogueCommands,
+        mut load_font: impl FnMut(&str) -> Handle<Font>,
+    ) -> Result<Vec<Paragraph>, DialogueAssetError> {
+        self.paragraphs
+            .into_iter()
+            .enumerate()
+            .map(|(index, paragraph)| {
+                paragraph.into_paragraph(&format!("paragraph {}", index), commands, &mut load_font)
+            })
+            .collect()
+    }
+}
+
+impl ParagraphDescriptor {
+    fn into_paragraph(
+        self,
+        location: &str,
+        commands: &DialogueCommands,
+        load_font: &mut impl FnMut(&str) -> Handle<Font>,
+    ) -> Result<Paragraph, DialogueAssetError> {
+        let mut paragraph = Paragraph::new()
+            .with_sentences(
+                self.sentences
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, sentence)| {
+                        sentence.into_sentence(
+                            &format!("{}, sentence {}", location, index),
+                            commands,
+                            load_font,
+                        )
+                    })
+                    .collect::<Result<_, _>>()?,
+            )
+            .with_variable_changes(variable_changes(self.set, self.add))
+            .with_commands(
+                self.commands
+                    .iter()
+                    .map(|call| parse_command(call, commands, location))
+                    .collect::<Result<_, _>>()?,
+            )
+            .with_stop(self.stop);
+
+        if let Some(name) = self.name {
+            paragraph.set_name(name);
+        }
+        if let Some(jump) = self.jump {
+            paragraph.set_jump(jump);
+        }
+        if let Some(condition) = self.condition {
+            paragraph.set_condition(parse_condition(&condition, location)?);
+        }
+        if let Some(position) = self.position {
+            paragraph.set_position(position);
+        }
+        if let Some(width) = self.width {
+            paragraph.set_width(width);
+        }
+        if let Some(choice) = self.choice {
+            paragraph.set_choice(choice.into_choice(location, commands, load_font)?);
+        }
+
+        Ok(paragraph)
+    }
+}
+
+impl SentenceDescriptor {
+    fn into_sentence(
+        self,
+        location: &str,
+        commands: &DialogueCommands,
+        load_font: &mut impl FnMut(&str) -> Handle<Font>,
+    ) -> Result<Sentence, DialogueAssetError> {
+        let mut sentence = Sentence::new()
+            .with_text(self.text)
+            .with_variable_changes(variable_changes(self.set, self.add));
+
+        if let Some(color) = self.color {
+            sentence.set_color(color.into_color(location)?);
+        }
+        if let Some(markup) = self.markup {
+            sentence.set_markup(&markup);
+        }
+        if let Some(font) = self.font {
+            sentence.set_font(load_font(&font));
+        }
+        if let Some(font) = self.bold_font {
+            sentence.set_bold_font(load_font(&font));
+        }
+        if let Some(size) = self.font_size {
+            sentence.set_font_size(size);
+        }
+        self.effects
+            .into_iter()
+            .for_each(|effect| sentence.add_effect(effect));
+        if let Some(typewriter) = self.typewriter {
+            let mut created = TypeWriter::new();
+            if let Some(speed) = typewriter.speed {
+                created.set_speed(speed);
+            }
+            if let Some(mode) = typewriter.mode {
+                created.set_mode(mode);
+            }
+            if let Some(reveal) = typewriter.reveal {
+                created.set_reveal(reveal);
+            }
+            sentence.set_typewriter(created);
+        }
+        if let Some(speaker) = self.speaker {
+            sentence.set_speaker(speaker);
+        }
+        if let Some(id) = self.line_id {
+            sentence.set_line_id(id);
+        }
+        if let Some(jump) = self.jump {
+            sentence.set_jump(jump);
+        }
+        if let Some(condition) = self.condition {
+            sentence.set_condition(parse_condition(&condition, location)?);
+        }
+        if let Some(call) = self.command {
+            sentence.set_sentence_action(SentenceAction::Command(parse_command(
+                &call, commands, location,
+            )?));
+        }
+        if let Some(timing) = self.action_timing {
+            sentence.set_action_timing(timing);
+        }
+        sentence.set_blocking_action(self.blocking);
+        if let Some(wait) = self.wait {
+            sentence.set_wait(wait);
+        }
+
+        Ok(sentence)
+    }
+}
+
+impl ChoiceDescriptor {
+    fn into_choice(
+        self,
+        location: &str,
+        commands: &DialogueCommands,
+        load_font: &mut impl FnMut(&str) -> Handle<Font>,
+    ) -> Result<Choice, DialogueAssetError> {
+        let mut choice = Choice::new().with_options(
+            self.options
+                .into_iter()
+                .enumerate()
+                .map(|(index, option)| {
+                    option.into_option(
+                        &format!("{}, option {}", location, index),
+                        commands,
+                        load_font,
+                    )
+                })
+                .collect::<Result<_, _>>()?,
+        );
+
+        if let Some(highlight) = self.highlight {
+            choice.set_highlight(highlight.into_color(location)?);
+        }
+
+        Ok(choice)
+    }
+}
+
+impl ChoiceOptionDescriptor {
+    fn into_option(
+        self,
+        location: &str,
+        commands: &DialogueCommands,
+        load_font: &mut impl FnMut(&str) -> Handle<Font>,
+    ) -> Result<ChoiceOption, DialogueAssetError> {
+        let mut option = ChoiceOption::default()
+            .with_label(self.label.into_sentence(location, commands, load_font)?)
+            .with_paragraphs(
+                self.paragraphs
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, paragraph)| {
+                        paragraph.into_paragraph(
+                            &format!("{}, paragraph {}", location, index),
+                            commands,
+                            load_font,
+                        )
+                    })
+                    .collect::<Result<_, _>>()?,
+            );
+
+        if let Some(jump) = self.jump {
+            option.set_jump(jump);
+        }
+        if let Some(condition) = self.condition {
+            option.set_condition(parse_condition(&condition, location)?);
+        }
+
+        Ok(option)
+    }
+}
+
+impl ColorDescriptor {
+    fn into_color(self, location: &str) -> Result<Color, DialogueAssetError> {
+        match self {
+            Self::Hex(hex) => Color::hex(&hex).map_err(|_| DialogueAssetError::Color {
+                location: location.to_string(),
+                color: hex,
+            }),
+            Self::Color(color) => Ok(color),
+        }
+    }
+}
+
+fn variable_changes(
+    set: BTreeMap<String, DialogueValue>,
+    add: BTreeMap<String, DialogueValue>,
+) -> Vec<VariableChange> {
+    set.into_iter()
+        .map(|(name, value)| VariableChange::Set(name, value))
+        .chain(
+            add.into_iter()
+                .map(|(name, value)| VariableChange::Add(name, value)),
+        )
+        .collect()
+}
+
+fn parse_condition(
+    condition: &str,
+    location: &str,
+) -> Result<DialogueCondition, DialogueAssetError> {
+    DialogueCondition::parse(condition).map_err(|error| DialogueAssetError::Condition {
+        location: location.to_string(),
+        error,
+    })
+}
+
+fn parse_command(
+    call: &str,
+    commands: &DialogueCommands,
+    location: &str,
+) -> Result<DialogueCommand, DialogueAssetError> {
+    commands
+        .parse(call)
+        .map_err(|error| DialogueAssetError::Command {
+            location: location.to_string(),
+            error,
+        })
+}
+
+#[derive(Debug)]
+pub enum DialogueAssetError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+    Json(serde_json::Error),
+    Condition {
+        location: String,
+        error: ConditionError,
+    },
+    Color {
+        location: String,
+        color: String,
+    },
+    Command {
+        location: String,
+        error: CommandError,
+    },
+    Yarn(YarnError),
+    Ink(InkError),
+}
+impl Display for DialogueAssetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "could not read dialogue: {}", error),
+            Self::Ron(error) => write!(f, "could not parse dialogue: {}", error),
+            Self::Json(error) => write!(f, "could not parse dialogue: {}", error),
+            Self::Condition { location, error } => {
+                write!(f, "invalid condition in {}: {}", location, error)
+            }
+            Self::Color { location, color } => {
+                write!(f, "invalid color {:?} in {}", color, location)
+            }
+            Self::Command { location, error } => {
+                write!(f, "invalid command in {}: {}", location, error)
+            }
+            Self::Yarn(error) => write!(f, "could not import yarn script:\n{}", error),
+            Self::Ink(error) => write!(f, "could not import ink story:\n{}", error),
+        }
+    }
+}
+impl std::error::Error for DialogueAssetError {}
+impl From<std::io::Error> for DialogueAssetError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+impl From<ron::error::SpannedError> for DialogueAssetError {
+    fn from(error: ron::error::SpannedError) -> Self {
+        Self::Ron(error)
+    }
+}
+impl From<serde_json::Error> for DialogueAssetError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Json(error)
+    }
+}
+
+/// Loads `.dialogue.ron` and `.dialogue.json` files, checking their calls against the [`DialogueCommands`] resource,
+/// including commands added to it once the app is running
+pub struct DialogueAssetLoader {
+    commands: DialogueCommands,
+}
+impl FromWorld for DialogueAssetLoader {
+    fn from_world(world: &mut World) -> Self {
+        Self {
+            commands: world
+                .get_resource::<DialogueCommands>()
+                .cloned()
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl AssetLoader for DialogueAssetLoader {
+    type Asset = DialogueAsset;
+    type Settings = ();
+    type Error = DialogueAssetError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a Self::Settings,
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+
+            let is_json = load_context
+                .path()
+                .extension()
+                .is_some_and(|extension| extension == "json");
+            let descriptor: DialogueDescriptor = if is_json {
+                serde_json::from_slice(&bytes)?
+            } else {
+                ron::de::from_bytes(&bytes)?
+            };
+
+            Ok(DialogueAsset::new(
+                descriptor.into_paragraphs_with(&self.commands, |path| {
+                    load_context.load(path.to_string())
+                })?,
+            ))
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["dialogue.ron", "dialogue.json"]
+    }
+}
@@ -0,0 +1,286 @@
+use crate::prelude::*;
+
+#[derive(Default, Debug, Clone)]
+pub struct ChoiceOption {
+    label: Sentence,
+    paragraphs: Vec<Paragraph>,
+    jump: Option<String>,
+    condition: Option<DialogueCondition>,
+    hidden: bool,
+}
+impl ChoiceOption {
+    /// ```
+    /// # use bevy_dialogue_system::prelude::*;
+    /// let option = ChoiceOption::new("Open the door");
+    ///
+    /// assert_eq!(option.label().text(), "Open the door");
+    /// ```
+    pub fn new(label: impl ToString) -> Self {
+        Self {
+            label: Sentence::new().with_text(label),
+            paragraphs: Vec::new(),
+            jump: None,
+            condition: None,
+            hidden: false,
+        }
+    }
+
+    pub fn with_label(mut self, label: Sentence) -> Self {
+        self.label = label;
+        self
+    }
+    pub fn set_label(&mut self, label: Sentence) {
+        self.label = label;
+    }
+    pub fn label(&self) -> &Sentence {
+        &self.label
+    }
+    pub fn label_mut(&mut self) -> &mut Sentence {
+        &mut self.label
+    }
+
+    pub fn with_paragraphs(mut self, paragraphs: Vec<Paragraph>) -> Self {
+        self.paragraphs = paragraphs;
+        self
+    }
+    pub fn push_paragraph(mut self, paragraph: Paragraph) -> Self {
+        self.paragraphs.push(paragraph);
+        self
+    }
+    pub fn set_paragraphs(&mut self, paragraphs: Vec<Paragraph>) {
+        self.paragraphs = paragraphs;
+    }
+    pub fn add_paragraph(&mut self, paragraph: Paragraph) {
+        self.paragraphs.push(paragraph);
+    }
+    pub fn paragraphs(&self) -> &Vec<Paragraph> {
+        &self.paragraphs
+    }
+    pub fn paragraphs_mut(&mut self) -> &mut Vec<Paragraph> {
+        &mut self.paragraphs
+    }
+
+    /// The name of the paragraph to go to once the paragraphs of this option have been shown
+    pub fn with_jump(mut self, target: impl ToString) -> Self {
+        self.jump = Some(target.to_string());
+        self
+    }
+    pub fn without_jump(mut self) -> Self {
+        self.jump = None;
+        self
+    }
+    pub fn set_jump(&mut self, target: impl ToString) {
+        self.jump = Some(target.to_string());
+    }
+    pub fn reset_jump(&mut self) {
+        self.jump = None;
+    }
+    pub fn get_jump(&self) -> Option<&String> {
+        self.jump.as_ref()
+    }
+
+    /// Only offer the option if `condition` holds when the choice is reached
+    pub fn with_condition(mut self, condition: DialogueCondition) -> Self {
+        self.condition = Some(condition);
+        self
+    }
+    pub fn without_condition(mut self) -> Self {
+        self.condition = None;
+        self
+    }
+    pub fn set_condition(&mut self, condition: DialogueCondition) {
+        self.condition = Some(condition);
+    }
+    pub fn reset_condition(&mut self) {
+        self.condition = None;
+    }
+    pub fn get_condition(&self) -> Option<&DialogueCondition> {
+        self.condition.as_ref()
+    }
+    /// Whether the option is left out because its condition didn't hold
+    pub fn is_hidden(&self) -> bool {
+        self.hidden
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Choice {
+    options: Vec<ChoiceOption>,
+    selected: usize,
+    style: Style,
+    highlight: Color,
+}
+impl Default for Choice {
+    fn default() -> Self {
+        Self {
+            options: Vec::new(),
+            selected: 0,
+            style: Style {
+                flex_direction: FlexDirection::Column,
+                ..Default::default()
+            },
+            highlight: Color::rgba(1., 1., 1., 0.2),
+        }
+    }
+}
+impl Choice {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// ```
+    /// # use bevy_dialogue_system::prelude::*;
+    /// let choice = Choice::new().with_options(vec![
+    ///     ChoiceOption::new("Yes"),
+    ///     ChoiceOption::new("No"),
+    /// ]);
+    ///
+    /// assert_eq!(choice.options().len(), 2);
+    /// assert_eq!(choice.selected(), 0);
+    /// ```
+    pub fn with_options(mut self, options: Vec<ChoiceOption>) -> Self {
+        self.options = options;
+        self
+    }
+    pub fn push_option(mut self, option: ChoiceOption) -> Self {
+        self.options.push(option);
+        self
+    }
+    pub fn set_options(&mut self, options: Vec<ChoiceOption>) {
+        self.options = options;
+        self.selected = 0;
+    }
+    pub fn add_option(&mut self, option: ChoiceOption) {
+        self.options.push(option);
+    }
+    pub fn options(&self) -> &Vec<ChoiceOption> {
+        &self.options
+    }
+    pub fn options_mut(&mut self) -> &mut Vec<ChoiceOption> {
+        &mut self.options
+    }
+    pub fn len(&self) -> usize {
+        self.options.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.options.is_empty()
+    }
+    pub fn has_visible_options(&self) -> bool {
+        self.options.iter().any(|option| !option.hidden)
+    }
+    /// Selects the first option and shows every option again
+    pub fn reset(&mut self) {
+        self.selected = 0;
+        self.options
+            .iter_mut()
+            .for_each(|option| option.hidden = false);
+    }
+    /// Hides the options whose conditions don't hold, moving the selection off of them
+    /// ```
+    /// # use bevy_dialogue_system::prelude::*;
+    /// let mut choice = Choice::new().with_options(vec![
+    ///     ChoiceOption::new("Pay 10 gold").with_condition(DialogueCondition::parse("gold >= 10").unwrap()),
+    ///     ChoiceOption::new("Leave"),
+    /// ]);
+    ///
+    /// choice.update_options(&DialogueVariables::new().with("gold", 3));
+    ///
+    /// assert!(choice.options()[0].is_hidden());
+    /// assert_eq!(choice.selected(), 1);
+    /// ```
+    pub fn update_options(&mut self, variables: &DialogueVariables) {
+        self.options.iter_mut().for_each(|option| {
+            option.hidden = option
+                .condition
+                .as_ref()
+                .is_some_and(|condition| !condition.evaluate(variables));
+        });
+        if self
+            .options
+            .get(self.selected)
+            .is_some_and(|option| option.hidden)
+        {
+            self.select_next();
+        }
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+    pub fn select(&mut self, index: usize) {
+        if self.options.get(index).is_some_and(|option| !option.hidden) {
+            self.selected = index;
+        }
+    }
+    /// Moves the selection up one visible option, wrapping around to the bottom
+    /// ```
+    /// # use bevy_dialogue_system::prelude::*;
+    /// let mut choice = Choice::new().with_options(vec![
+    ///     ChoiceOption::new("Yes"),
+    ///     ChoiceOption::new("No"),
+    ///     ChoiceOption::new("Maybe"),
+    /// ]);
+    ///
+    /// choice.select_previous();
+    /// assert_eq!(choice.selected(), 2);
+    ///
+    /// choice.select_next();
+    /// assert_eq!(choice.selected(), 0);
+    /// ```
+    pub fn select_previous(&mut self) {
+        let length = self.options.len();
+        if let Some(previous) = (1..=length)
+            .map(|offset| (self.selected + length * 2 - offset) % length)
+            .find(|index| !self.options[*index].hidden)
+        {
+            self.selected = previous;
+        }
+    }
+    /// Moves the selection down one visible option, wrapping around to the top
+    pub fn select_next(&mut self) {
+        let length = self.options.len();
+        if let Some(next) = (1..=length)
+            .map(|offset| (self.selected + offset) % length)
+            .find(|index| !self.options[*index].hidden)
+        {
+            self.selected = next;
+        }
+    }
+
+    /// The style of the node that holds the options
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
+    /// The background color of the currently selected option
+    pub fn with_highlight(mut self, color: Color) -> Self {
+        self.highlight = color;
+        self
+    }
+    pub fn set_highlight(&mut self, color: Color) {
+        self.highlight = color;
+    }
+    pub fn highlight(&self) -> &Color {
+        &self.highlight
+    }
+}
+
+/// The node holding the options of the current [`Choice`] of a [`Dialogue`]
+#[derive(Component)]
+pub struct ChoiceList {
+    pub dialogue: Entity,
+}
+
+/// A single selectable option within a [`ChoiceList`]
+#[derive(Component)]
+pub struct ChoiceButton {
+    pub dialogue: Entity,
+    pub index: usize,
+}
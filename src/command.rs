@@ -0,0 +1,355 @@
+use crate::prelude::*;
+use bevy::ecs::system::{BoxedSystem, SystemId};
+use std::sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// The kind of value a command expects for one of its arguments
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandArgument {
+    Bool,
+    Int,
+    /// Whole numbers are accepted too
+    Float,
+    /// Use quotes for strings with spaces, such as `"old key"`
+    String,
+}
+impl CommandArgument {
+    /// Strings are parsed into the expected kind, as that is how arguments are written in dialogue files
+    fn convert(&self, argument: &DialogueValue) -> Option<DialogueValue> {
+        match (self, argument) {
+            (Self::Bool, DialogueValue::String(word)) => word.parse().ok().map(DialogueValue::Bool),
+            (Self::Int, DialogueValue::String(word)) => word.parse().ok().map(DialogueValue::Int),
+            (Self::Float, DialogueValue::String(word)) => {
+                word.parse().ok().map(DialogueValue::Float)
+            }
+            (Self::Bool, DialogueValue::Bool(_)) | (Self::Int, DialogueValue::Int(_)) => {
+                Some(argument.clone())
+            }
+            (Self::Float, argument) => argument.as_f64().map(DialogueValue::Float),
+            (Self::String, argument) => Some(DialogueValue::String(argument.to_string())),
+            _ => None,
+        }
+    }
+}
+impl Display for CommandArgument {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Bool => write!(f, "a bool"),
+            Self::Int => write!(f, "an int"),
+            Self::Float => write!(f, "a float"),
+            Self::String => write!(f, "a string"),
+        }
+    }
+}
+
+/// A call to a command of [`DialogueCommands`] such as `<<give_item sword 1>>`, its arguments already checked
+#[derive(Debug, Clone, PartialEq)]
+pub struct DialogueCommand {
+    pub name: String,
+    pub arguments: Vec<DialogueValue>,
+}
+impl Display for DialogueCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<<{}", self.name)?;
+        self.arguments
+            .iter()
+            .try_for_each(|argument| write!(f, " {}", argument))?;
+        write!(f, ">>")
+    }
+}
+
+/// What a command's handler is given when it runs
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandInput {
+    /// The entity of the dialogue the command comes from
+    pub dialogue: Entity,
+    pub arguments: Vec<DialogueValue>,
+}
+
+struct RegisteredCommand {
+    arguments: Vec<CommandArgument>,
+    /// Waits here until the command first runs, as registering it needs the world
+    system: Option<BoxedSystem<CommandInput>>,
+    id: Option<SystemId<CommandInput>>,
+}
+
+/// Named commands that dialogue files can call, such as `commands: ["give_item sword 1"]` on a paragraph or
+/// `command: Some("play_sound door.ogg")` on a sentence of a `.dialogue.ron` file, or `<<give_item sword 1>>`
+/// on its own line in a `.yarn` file
+///
+/// Calls are checked against the commands when the file loads, so a misspelled name or a wrong argument fails the
+/// load instead of doing nothing later on. Each handler is registered as a one-shot system the first time it runs,
+/// so it keeps its `Local`s and event readers between calls.
+/// ```
+/// # use bevy_dialogue_system::prelude::*;
+/// # use bevy::prelude::*;
+/// #[derive(Resource, Default)]
+/// struct Inventory(Vec<(String, i64)>, usize);
+///
+/// fn give_item(In(input): In<CommandInput>, mut inventory: ResMut<Inventory>, mut calls: Local<usize>) {
+///     *calls += 1;
+///     inventory.1 = *calls;
+///     if let [DialogueValue::String(item), DialogueValue::Int(count)] = input.arguments.as_slice() {
+///         inventory.0.push((item.clone(), *count));
+///     }
+/// }
+///
+/// let commands = DialogueCommands::new()
+///     .with("give_item", [CommandArgument::String, CommandArgument::Int], give_item);
+///
+/// let command = commands.parse("<<give_item \"old key\" 2>>").unwrap();
+/// assert_eq!(command.arguments, vec![DialogueValue::from("old key"), DialogueValue::from(2)]);
+///
+/// assert_eq!(
+///     commands.parse("give_item sword lots").unwrap_err().to_string(),
+///     "argument 2 of <<give_item>> should be an int, found \"lots\"",
+/// );
+/// assert_eq!(commands.parse("set_bg forest").unwrap_err().to_string(), "unknown command <<set_bg>>");
+///
+/// let mut app = App::new();
+/// app.add_plugins((MinimalPlugins, AssetPlugin::default(), bevy::input::InputPlugin, DialoguePlugin))
+///     .insert_resource(commands.clone())
+///     .init_resource::<Inventory>();
+/// # app.finish();
+/// # app.cleanup();
+///
+/// let paragraphs = DialogueDescriptor {
+///     paragraphs: vec![ParagraphDescriptor {
+///         commands: vec!["give_item sword 1".to_string()],
+///         sentences: vec![SentenceDescriptor { text: "Here, take this".to_string(), ..default() }],
+///         ..default()
+///     }],
+/// }
+/// .into_paragraphs_with(&commands, |_| Handle::default())
+/// .unwrap();
+///
+/// app.world.spawn(Dialogue::new().with_paragraphs(paragraphs.clone()));
+/// app.update();
+/// assert_eq!(app.world.resource::<Inventory>().0, vec![("sword".to_string(), 1)]);
+///
+/// app.world.spawn(Dialogue::new().with_paragraphs(paragraphs));
+/// app.update();
+/// assert_eq!(app.world.resource::<Inventory>().1, 2);
+///
+/// // The resource is shared with the loaders and `commands`
+/// app.world.resource_mut::<DialogueCommands>().add("wave", [], |_: In<CommandInput>| {});
+/// assert!(commands.contains("wave"));
+/// ```
+#[derive(Resource, Default, Clone)]
+pub struct DialogueCommands(Arc<RwLock<HashMap<String, RegisteredCommand>>>);
+
+impl DialogueCommands {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a command taking the given arguments, `handler` being a system that takes [`CommandInput`]
+    pub fn with<M>(
+        mut self,
+        name: impl ToString,
+        arguments: impl IntoIterator<Item = CommandArgument>,
+        handler: impl IntoSystem<CommandInput, (), M>,
+    ) -> Self {
+        self.add(name, arguments, handler);
+        self
+    }
+    /// Clones of the registry share its commands, so ones added to the resource once the app is running are still
+    /// known to the loaders. Inserting a whole new resource once the app is running isn't, add to it instead
+    pub fn add<M>(
+        &mut self,
+        name: impl ToString,
+        arguments: impl IntoIterator<Item = CommandArgument>,
+        handler: impl IntoSystem<CommandInput, (), M>,
+    ) {
+        self.write().insert(
+            name.to_string(),
+            RegisteredCommand {
+                arguments: arguments.into_iter().collect(),
+                system: Some(Box::new(IntoSystem::into_system(handler))),
+                id: None,
+            },
+        );
+    }
+    pub fn remove(&mut self, name: &str) {
+        self.write().remove(name);
+    }
+    pub fn contains(&self, name: &str) -> bool {
+        self.read().contains_key(name)
+    }
+    /// The arguments the command expects
+    pub fn arguments(&self, name: &str) -> Option<Vec<CommandArgument>> {
+        self.read()
+            .get(name)
+            .map(|command| command.arguments.clone())
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, HashMap<String, RegisteredCommand>> {
+        self.0.read().unwrap_or_else(PoisonError::into_inner)
+    }
+    fn write(&self) -> RwLockWriteGuard<'_, HashMap<String, RegisteredCommand>> {
+        self.0.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Runs the command's handler, registering it as a system the first time
+    pub(crate) fn run(
+        &self,
+        command: &DialogueCommand,
+        dialogue: Entity,
+        world: &mut World,
+    ) -> Result<(), String> {
+        let id = {
+            let mut commands = self.write();
+            let registered = commands
+                .get_mut(&command.name)
+                .ok_or_else(|| format!("unknown command {}", command))?;
+
+            match (registered.id, registered.system.take()) {
+                (Some(id), _) => id,
+                (None, Some(system)) => *registered.id.insert(world.register_boxed_system(system)),
+                (None, None) => return Err(format!("command {} has no handler", command)),
+            }
+        };
+
+        world
+            .run_system_with_input(
+                id,
+                CommandInput {
+                    dialogue,
+                    arguments: command.arguments.clone(),
+                },
+            )
+            .map_err(|error| error.to_string())
+    }
+
+    /// Checks a call such as `give_item sword 1`, with or without the surrounding `<<` and `>>`
+    pub fn parse(&self, call: &str) -> Result<DialogueCommand, CommandError> {
+        let call = call.trim();
+        let call = call
+            .strip_prefix("<<")
+            .and_then(|call| call.strip_suffix(">>"))
+            .unwrap_or(call);
+
+        let mut words = split_words(call).into_iter();
+        let name = words.next().ok_or(CommandError::Empty)?;
+        self.check(name, words.map(DialogueValue::String).collect())
+    }
+
+    /// Checks a call whose arguments are already values, converting them to the kinds the command expects
+    /// ```
+    /// # use bevy_dialogue_system::prelude::*;
+    /// # use bevy::prelude::*;
+    /// let commands = DialogueCommands::new()
+    ///     .with("shake", [CommandArgument::Float], |_: In<CommandInput>| {});
+    ///
+    /// let command = commands.check("shake", vec![DialogueValue::from(2)]).unwrap();
+    /// assert_eq!(command.arguments, vec![DialogueValue::Float(2.)]);
+    /// assert!(commands.check("shake", vec![DialogueValue::from(true)]).is_err());
+    /// ```
+    pub fn check(
+        &self,
+        name: impl ToString,
+        arguments: Vec<DialogueValue>,
+    ) -> Result<DialogueCommand, CommandError> {
+        let name = name.to_string();
+        let commands = self.read();
+        let Some(command) = commands.get(&name) else {
+            return Err(CommandError::Unknown(name));
+        };
+        if arguments.len() != command.arguments.len() {
+            return Err(CommandError::ArgumentCount {
+                expected: command.arguments.len(),
+                found: arguments.len(),
+                name,
+            });
+        }
+
+        let arguments = command
+            .arguments
+            .iter()
+            .zip(arguments)
+            .enumerate()
+            .map(|(index, (kind, argument))| {
+                kind.convert(&argument)
+                    .ok_or_else(|| CommandError::InvalidArgument {
+                        name: name.clone(),
+                        index,
+                        expected: *kind,
+                        found: argument.to_string(),
+                    })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(DialogueCommand { name, arguments })
+    }
+}
+
+/// Splits on whitespace, keeping anything between double quotes together
+fn split_words(call: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut quoted = false;
+
+    call.chars().for_each(|character| match character {
+        '"' => {
+            quoted = !quoted;
+            word.get_or_insert_with(String::new);
+        }
+        character if character.is_whitespace() && !quoted => words.extend(word.take()),
+        character => word.get_or_insert_with(String::new).push(character),
+    });
+    words.extend(word);
+
+    words
+}
+
+/// Why a call doesn't match any command of [`DialogueCommands`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum CommandError {
+    Empty,
+    Unknown(String),
+    ArgumentCount {
+        name: String,
+        expected: usize,
+        found: usize,
+    },
+    /// `index` starts at 0
+    InvalidArgument {
+        name: String,
+        index: usize,
+        expected: CommandArgument,
+        found: String,
+    },
+}
+impl Display for CommandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "expected a command name"),
+            Self::Unknown(name) => write!(f, "unknown command <<{}>>", name),
+            Self::ArgumentCount {
+                name,
+                expected,
+                found,
+            } => write!(
+                f,
+                "<<{}>> takes {} argument{}, found {}",
+                name,
+                expected,
+                if *expected == 1 { "" } else { "s" },
+                found
+            ),
+            Self::InvalidArgument {
+                name,
+                index,
+                expected,
+                found,
+            } => write!(
+                f,
+                "argument {} of <<{}>> should be {}, found {:?}",
+                index + 1,
+                name,
+                expected,
+                found
+            ),
+        }
+    }
+}
+impl std::error::Error for CommandError {}
@@ -0,0 +1,344 @@
+use crate::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Equal,
+    NotEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+}
+impl Comparison {
+    pub fn compare(&self, left: &DialogueValue, right: &DialogueValue) -> bool {
+        use std::cmp::Ordering;
+
+        let ordering = left.compare(right);
+        match self {
+            Self::Equal => ordering == Some(Ordering::Equal),
+            Self::NotEqual => ordering != Some(Ordering::Equal),
+            Self::Less => ordering == Some(Ordering::Less),
+            Self::LessEqual => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+            Self::Greater => ordering == Some(Ordering::Greater),
+            Self::GreaterEqual => matches!(ordering, Some(Ordering::Greater | Ordering::Equal)),
+        }
+    }
+}
+
+/// Decides whether a [`Paragraph`] or [`Sentence`] is shown, based on the current [`DialogueVariables`]
+///
+/// Conditions can be written out as text, using `==`, `!=`, `<`, `<=`, `>`, `>=`, `&&`, `||`, `!` and brackets
+/// ```
+/// # use bevy_dialogue_system::prelude::*;
+/// let variables = DialogueVariables::new().with("has_key", true).with("gold", 12);
+///
+/// assert!(DialogueCondition::parse("has_key == true").unwrap().evaluate(&variables));
+/// assert!(DialogueCondition::parse("gold >= 10 && !(name == \"Bob\")").unwrap().evaluate(&variables));
+/// assert!(!DialogueCondition::parse("has_map").unwrap().evaluate(&variables));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum DialogueCondition {
+    /// True when the variable exists and is truthy
+    Variable(String),
+    Literal(DialogueValue),
+    Compare(Box<DialogueCondition>, Comparison, Box<DialogueCondition>),
+    Not(Box<DialogueCondition>),
+    And(Box<DialogueCondition>, Box<DialogueCondition>),
+    Or(Box<DialogueCondition>, Box<DialogueCondition>),
+}
+impl DialogueCondition {
+    pub fn parse(condition: &str) -> Result<Self, ConditionError> {
+        let tokens = tokenize(condition)?;
+        let mut parser = Parser {
+            tokens: &tokens,
+            position: 0,
+            length: condition.len(),
+        };
+
+        let condition = parser.or()?;
+        match parser.peek() {
+            Some((column, token)) => Err(ConditionError::new(
+                format!("unexpected {:?}", token),
+                *column,
+            )),
+            None => Ok(condition),
+        }
+    }
+
+    pub fn variable(name: impl ToString) -> Self {
+        Self::Variable(name.to_string())
+    }
+    /// ```
+    /// # use bevy_dialogue_system::prelude::*;
+    /// let condition = DialogueCondition::compare("gold", Comparison::GreaterEqual, 10);
+    ///
+    /// assert!(!condition.evaluate(&DialogueVariables::new().with("gold", 9)));
+    /// assert!(condition.evaluate(&DialogueVariables::new().with("gold", 10)));
+    /// ```
+    pub fn compare(
+        name: impl ToString,
+        comparison: Comparison,
+        value: impl Into<DialogueValue>,
+    ) -> Self {
+        Self::Compare(
+            Box::new(Self::variable(name)),
+            comparison,
+            Box::new(Self::Literal(value.into())),
+        )
+    }
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(self) -> Self {
+        Self::Not(Box::new(self))
+    }
+    pub fn and(self, other: DialogueCondition) -> Self {
+        Self::And(Box::new(self), Box::new(other))
+    }
+    pub fn or(self, other: DialogueCondition) -> Self {
+        Self::Or(Box::new(self), Box::new(other))
+    }
+
+    /// Variables that don't exist are false, and are not equal to anything
+    pub fn evaluate(&self, variables: &DialogueVariables) -> bool {
+        match self {
+            Self::Variable(name) => variables.get(name).is_some_and(|value| value.is_truthy()),
+            Self::Literal(value) => value.is_truthy(),
+            Self::Compare(left, comparison, right) => {
+                match (left.value(variables), right.value(variables)) {
+                    (Some(left), Some(right)) => comparison.compare(&left, &right),
+                    _ => *comparison == Comparison::NotEqual,
+                }
+            }
+            Self::Not(condition) => !condition.evaluate(variables),
+            Self::And(left, right) => left.evaluate(variables) && right.evaluate(variables),
+            Self::Or(left, right) => left.evaluate(variables) || right.evaluate(variables),
+        }
+    }
+    fn value(&self, variables: &DialogueVariables) -> Option<DialogueValue> {
+        match self {
+            Self::Variable(name) => variables.get(name).cloned(),
+            Self::Literal(value) => Some(value.clone()),
+            condition => Some(DialogueValue::Bool(condition.evaluate(variables))),
+        }
+    }
+}
+impl std::str::FromStr for DialogueCondition {
+    type Err = ConditionError;
+
+    fn from_str(condition: &str) -> Result<Self, Self::Err> {
+        Self::parse(condition)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConditionError {
+    message: String,
+    column: usize,
+}
+impl ConditionError {
+    fn new(message: impl ToString, column: usize) -> Self {
+        Self {
+            message: message.to_string(),
+            column,
+        }
+    }
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+    /// The byte offset into the condition where the problem was found
+    pub fn column(&self) -> usize {
+        self.column
+    }
+}
+impl Display for ConditionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at column {}", self.message, self.column + 1)
+    }
+}
+impl std::error::Error for ConditionError {}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Identifier(String),
+    Value(DialogueValue),
+    Comparison(Comparison),
+    And,
+    Or,
+    Not,
+    Open,
+    Close,
+}
+
+fn tokenize(condition: &str) -> Result<Vec<(usize, Token)>, ConditionError> {
+    let mut tokens = Vec::new();
+    let mut characters = condition.char_indices().peekable();
+
+    while let Some((column, character)) = characters.next() {
+        let mut next_is =
+            |expected: char| characters.next_if(|(_, next)| *next == expected).is_some();
+
+        let token = match character {
+            character if character.is_whitespace() => continue,
+            '(' => Token::Open,
+            ')' => Token::Close,
+            '&' if next_is('&') => Token::And,
+            '|' if next_is('|') => Token::Or,
+            '=' if next_is('=') => Token::Comparison(Comparison::Equal),
+            '!' if next_is('=') => Token::Comparison(Comparison::NotEqual),
+            '!' => Token::Not,
+            '<' if next_is('=') => Token::Comparison(Comparison::LessEqual),
+            '<' => Token::Comparison(Comparison::Less),
+            '>' if next_is('=') => Token::Comparison(Comparison::GreaterEqual),
+            '>' => Token::Comparison(Comparison::Greater),
+            '"' | '\'' => {
+                let mut value = String::new();
+                loop {
+                    match characters.next() {
+                        Some((_, end)) if end == character => break,
+                        Some((_, '\\')) => {
+                            if let Some((_, escaped)) = characters.next() {
+                                value.push(escaped);
+                            }
+                        }
+                        Some((_, next)) => value.push(next),
+                        None => return Err(ConditionError::new("unterminated string", column)),
+                    }
+                }
+                Token::Value(DialogueValue::String(value))
+            }
+            character if character.is_ascii_digit() || character == '-' || character == '.' => {
+                let mut number = character.to_string();
+                while let Some((_, next)) =
+                    characters.next_if(|(_, next)| next.is_ascii_digit() || *next == '.')
+                {
+                    number.push(next);
+                }
+                if let Ok(value) = number.parse::<i64>() {
+                    Token::Value(DialogueValue::Int(value))
+                } else if let Ok(value) = number.parse::<f64>() {
+                    Token::Value(DialogueValue::Float(value))
+                } else {
+                    return Err(ConditionError::new(
+                        format!("invalid number {:?}", number),
+                        column,
+                    ));
+                }
+            }
+            character if character.is_alphanumeric() || character == '_' || character == '$' => {
+                let mut word = String::new();
+                if character != '$' {
+                    word.push(character);
+                }
+                while let Some((_, next)) = characters
+                    .next_if(|(_, next)| next.is_alphanumeric() || *next == '_' || *next == '.')
+                {
+                    word.push(next);
+                }
+                match word.as_str() {
+                    "true" => Token::Value(DialogueValue::Bool(true)),
+                    "false" => Token::Value(DialogueValue::Bool(false)),
+                    "and" => Token::And,
+                    "or" => Token::Or,
+                    "not" => Token::Not,
+                    "is" | "eq" => Token::Comparison(Comparison::Equal),
+                    "neq" => Token::Comparison(Comparison::NotEqual),
+                    "lt" => Token::Comparison(Comparison::Less),
+                    "lte" => Token::Comparison(Comparison::LessEqual),
+                    "gt" => Token::Comparison(Comparison::Greater),
+                    "gte" => Token::Comparison(Comparison::GreaterEqual),
+                    "" => return Err(ConditionError::new("expected a variable name", column)),
+                    _ => Token::Identifier(word),
+                }
+            }
+            character => {
+                return Err(ConditionError::new(
+                    format!("unexpected {:?}", character),
+                    column,
+                ))
+            }
+        };
+        tokens.push((column, token));
+    }
+
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    tokens: &'a [(usize, Token)],
+    position: usize,
+    length: usize,
+}
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&'a (usize, Token)> {
+        self.tokens.get(self.position)
+    }
+    fn next_if(&mut self, expected: &Token) -> bool {
+        let matches = self.peek().is_some_and(|(_, token)| token == expected);
+        if matches {
+            self.position += 1;
+        }
+        matches
+    }
+
+    fn or(&mut self) -> Result<DialogueCondition, ConditionError> {
+        let mut condition = self.and()?;
+        while self.next_if(&Token::Or) {
+            condition = condition.or(self.and()?);
+        }
+        Ok(condition)
+    }
+    fn and(&mut self) -> Result<DialogueCondition, ConditionError> {
+        let mut condition = self.not()?;
+        while self.next_if(&Token::And) {
+            condition = condition.and(self.not()?);
+        }
+        Ok(condition)
+    }
+    fn not(&mut self) -> Result<DialogueCondition, ConditionError> {
+        if self.next_if(&Token::Not) {
+            Ok(self.not()?.not())
+        } else {
+            self.comparison()
+        }
+    }
+    fn comparison(&mut self) -> Result<DialogueCondition, ConditionError> {
+        let left = self.operand()?;
+        match self.peek() {
+            Some((_, Token::Comparison(comparison))) => {
+                self.position += 1;
+                let right = self.operand()?;
+                Ok(DialogueCondition::Compare(
+                    Box::new(left),
+                    *comparison,
+                    Box::new(right),
+                ))
+            }
+            _ => Ok(left),
+        }
+    }
+    fn operand(&mut self) -> Result<DialogueCondition, ConditionError> {
+        let Some((column, token)) = self.peek() else {
+            return Err(ConditionError::new("unexpected end", self.length));
+        };
+        self.position += 1;
+
+        match token {
+            Token::Identifier(name) => Ok(DialogueCondition::Variable(name.clone())),
+            Token::Value(value) => Ok(DialogueCondition::Literal(value.clone())),
+            Token::Open => {
+                let condition = self.or()?;
+                if self.next_if(&Token::Close) {
+                    Ok(condition)
+                } else {
+                    Err(ConditionError::new(
+                        "expected a closing bracket",
+                        self.peek().map_or(self.length, |(column, _)| *column),
+                    ))
+                }
+            }
+            token => Err(ConditionError::new(
+                format!("unexpected {:?}", token),
+                *column,
+            )),
+        }
+    }
+}
@@ -1,18 +1,79 @@
-use crate::prelude::*;
+use crate::{action::AfterWait, event::Progress, prelude::*};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// How long auto mode waits once a line has been typed out before moving on,
+/// `base` seconds plus `per_character` seconds for every character of the line
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AutoAdvance {
+    pub base: f32,
+    pub per_character: f32,
+}
+impl Default for AutoAdvance {
+    fn default() -> Self {
+        Self::new(1., 0.05)
+    }
+}
+impl AutoAdvance {
+    pub fn new(base: f32, per_character: f32) -> Self {
+        Self {
+            base,
+            per_character,
+        }
+    }
+    /// How long to wait after a line with this text
+    pub fn delay(&self, text: &str) -> f32 {
+        self.base + self.per_character * text.graphemes(true).count() as f32
+    }
+}
 
 #[derive(Component)]
 pub struct Dialogue {
-    paragraphs: VecDeque<Paragraph>,
-    skip_keys: Vec<KeyCode>,
+    paragraphs: Vec<Paragraph>,
+    current_paragraph: usize,
+    /// Each paragraph with where it comes from, which is used to remember its lines have been seen
+    branch: VecDeque<(String, Paragraph)>,
+    bindings: DialogueBindings,
     hide_on_empty: bool,
+    pauses: TypeWriterPauses,
+    fast_forward: bool,
+    skipping: bool,
+    skip_interval: f32,
+    skip_seen_only: bool,
+    skip_timer: f32,
+    seen: HashSet<String>,
+    auto: bool,
+    auto_advance: AutoAdvance,
+    auto_timer: f32,
+    visits: usize,
+    reported: Option<Progress>,
+    started_commands: Vec<DialogueCommand>,
+    waiting: Option<AfterWait>,
+    wait_timer: f32,
 }
 
 impl Default for Dialogue {
     fn default() -> Self {
         Self {
-            paragraphs: VecDeque::new(),
-            skip_keys: vec![KeyCode::Space, KeyCode::Enter],
+            paragraphs: Vec::new(),
+            current_paragraph: 0,
+            branch: VecDeque::new(),
+            bindings: DialogueBindings::default(),
             hide_on_empty: true,
+            pauses: TypeWriterPauses::default(),
+            fast_forward: false,
+            skipping: false,
+            skip_interval: 0.1,
+            skip_seen_only: false,
+            skip_timer: 0.,
+            seen: HashSet::new(),
+            auto: false,
+            auto_advance: AutoAdvance::default(),
+            auto_timer: 0.,
+            visits: 0,
+            reported: None,
+            started_commands: Vec::new(),
+            waiting: None,
+            wait_timer: 0.,
         }
     }
 }
@@ -25,47 +86,334 @@ impl Dialogue {
     }
 
     pub fn with_paragraphs(mut self, paragraphs: Vec<Paragraph>) -> Self {
-        self.paragraphs = paragraphs.into();
+        self.set_paragraphs(paragraphs);
         self
     }
     pub fn push_paragraphs(mut self, paragraph: Paragraph) -> Self {
-        self.paragraphs.push_back(paragraph);
+        self.paragraphs.push(paragraph);
         self
     }
+    /// Replaces every paragraph and starts again from the first one
     pub fn set_paragraphs(&mut self, paragraphs: Vec<Paragraph>) {
-        self.paragraphs = paragraphs.into();
+        self.paragraphs = paragraphs;
+        self.restart();
     }
-    pub fn add_paragraphs(&mut self, paragraphs: Vec<Paragraph>) {
-        self.paragraphs.reserve_exact(paragraphs.len());
-        paragraphs
-            .into_iter()
-            .for_each(|paragraph| self.paragraphs.push_back(paragraph));
+    pub fn add_paragraphs(&mut self, mut paragraphs: Vec<Paragraph>) {
+        self.paragraphs.append(&mut paragraphs);
     }
     pub fn add_paragraph(&mut self, paragraph: Paragraph) {
-        self.paragraphs.push_back(paragraph);
+        self.paragraphs.push(paragraph);
     }
     pub fn get_current_paragraph(&self) -> Option<&Paragraph> {
-        self.paragraphs.front()
+        self.branch
+            .front()
+            .map(|(_, paragraph)| paragraph)
+            .or_else(|| self.paragraphs.get(self.current_paragraph))
     }
     pub fn get_current_paragraph_mut(&mut self) -> Option<&mut Paragraph> {
-        self.paragraphs.front_mut()
+        self.branch
+            .front_mut()
+            .map(|(_, paragraph)| paragraph)
+            .or_else(|| self.paragraphs.get_mut(self.current_paragraph))
     }
+    /// The index of the paragraph the dialogue is at, or will return to once the paragraphs of a picked option have been shown
+    pub fn current_index(&self) -> usize {
+        self.current_paragraph
+    }
+    /// Whether the current paragraph comes from a picked option rather than from the dialogue itself
+    pub fn in_branch(&self) -> bool {
+        !self.branch.is_empty()
+    }
+    /// Moves on from the current paragraph, following its jump if it has one
+    /// ```
+    /// # use bevy_dialogue_system::prelude::*;
+    /// let mut dialogue = Dialogue::new().with_paragraphs(vec![
+    ///     Paragraph::new().with_name("hub").with_sentences(vec![Sentence::new().with_text("Hello again")]),
+    ///     Paragraph::new().with_jump("hub").with_sentences(vec![Sentence::new().with_text("Back we go")]),
+    /// ]);
+    ///
+    /// dialogue.advance_paragraph();
+    /// assert_eq!(dialogue.current_index(), 1);
+    ///
+    /// dialogue.advance_paragraph();
+    /// assert_eq!(dialogue.current_index(), 0);
+    /// assert!(!dialogue.is_finished());
+    /// ```
     pub fn advance_paragraph(&mut self) {
-        self.paragraphs.pop_front();
+        if self
+            .get_current_paragraph()
+            .is_some_and(|paragraph| paragraph.stops() && !paragraph.is_skipped())
+        {
+            self.stop();
+            return;
+        }
+
+        let jump = self
+            .get_current_paragraph()
+            .filter(|paragraph| !paragraph.is_skipped())
+            .and_then(|paragraph| paragraph.get_jump())
+            .cloned();
+
+        if let Some(jump) = jump {
+            if self.goto(&jump) {
+                return;
+            }
+            warn!("Could not jump to missing paragraph {jump:?}, continuing on instead");
+        }
+
+        if self.branch.pop_front().is_none() {
+            self.current_paragraph = (self.current_paragraph + 1).min(self.paragraphs.len());
+        }
+        self.reset_current_paragraph();
+    }
+    /// Starts the current paragraph if it hasn't been already, passing over any that have nothing to show
+    /// ```
+    /// # use bevy_dialogue_system::prelude::*;
+    /// let mut variables = DialogueVariables::new();
+    /// let mut dialogue = Dialogue::new().with_paragraphs(vec![
+    ///     Paragraph::new()
+    ///         .with_condition(DialogueCondition::variable("has_key"))
+    ///         .with_sentences(vec![Sentence::new().with_text("You unlock the door")]),
+    ///     Paragraph::new().with_sentences(vec![Sentence::new().with_text("The door won't budge")]),
+    /// ]);
+    ///
+    /// dialogue.start_paragraph(&mut variables);
+    ///
+    /// assert_eq!(dialogue.current_index(), 1);
+    /// ```
+    pub fn start_paragraph(&mut self, variables: &mut DialogueVariables) {
+        // Bounded so that empty paragraphs jumping between each other can't hang the game
+        for _ in 0..=self.paragraphs.len() + self.branch.len() {
+            let Some(paragraph) = self.get_current_paragraph_mut() else {
+                return;
+            };
+
+            if paragraph.is_started() {
+                paragraph.skip_hidden_sentences(variables);
+                return;
+            }
+            let shown = paragraph.start(variables);
+            let commands = (!paragraph.is_skipped()).then(|| paragraph.commands().clone());
+            self.started_commands.extend(commands.into_iter().flatten());
+            if shown {
+                return;
+            }
+            self.advance_paragraph();
+        }
+        warn!(
+            "Passed over too many paragraphs with nothing to show, do some of them jump in a loop?"
+        );
+    }
+    /// The commands of the paragraphs started since this was last called
+    pub(crate) fn take_started_commands(&mut self) -> Vec<DialogueCommand> {
+        std::mem::take(&mut self.started_commands)
+    }
+    /// Jumps to the paragraph with the given name, abandoning the paragraphs of any picked option
+    ///
+    /// Returns false, leaving the dialogue untouched, when no paragraph has that name
+    /// ```
+    /// # use bevy_dialogue_system::prelude::*;
+    /// let mut dialogue = Dialogue::new().with_paragraphs(vec![
+    ///     Paragraph::new().with_sentences(vec![Sentence::new().with_text("Start")]),
+    ///     Paragraph::new().with_name("end").with_sentences(vec![Sentence::new().with_text("End")]),
+    /// ]);
+    ///
+    /// assert!(dialogue.goto("end"));
+    /// assert_eq!(dialogue.current_index(), 1);
+    ///
+    /// assert!(!dialogue.goto("middle"));
+    /// assert_eq!(dialogue.current_index(), 1);
+    /// ```
+    pub fn goto(&mut self, name: &str) -> bool {
+        let Some(index) = self.position_of(name) else {
+            return false;
+        };
+
+        self.branch.clear();
+        self.current_paragraph = index;
+        self.reset_current_paragraph();
+        true
+    }
+    /// Swaps in new versions of the paragraphs, keeping the current position if every paragraph still has the same
+    /// number of sentences and options and starting again from the beginning if not
+    ///
+    /// The paragraphs of a picked option that are still being shown are kept as they were
+    ///
+    /// Returns whether the current position was kept
+    /// ```
+    /// # use bevy_dialogue_system::prelude::*;
+    /// let mut dialogue = Dialogue::new().with_paragraphs(vec![
+    ///     Paragraph::new().with_sentences(vec![Sentence::new().with_text("Helo")]),
+    ///     Paragraph::new().with_sentences(vec![Sentence::new().with_text("Wrold")]),
+    /// ]);
+    /// dialogue.advance_paragraph();
+    ///
+    /// assert!(dialogue.reload_paragraphs(vec![
+    ///     Paragraph::new().with_sentences(vec![Sentence::new().with_text("Hello")]),
+    ///     Paragraph::new().with_sentences(vec![Sentence::new().with_text("World")]),
+    /// ]));
+    /// assert_eq!(dialogue.current_index(), 1);
+    /// assert_eq!(dialogue.get_current_paragraph().unwrap().sentences()[0].text(), "World");
+    ///
+    /// assert!(!dialogue.reload_paragraphs(vec![
+    ///     Paragraph::new().with_sentences(vec![Sentence::new().with_text("Hello there")]),
+    /// ]));
+    /// assert_eq!(dialogue.current_index(), 0);
+    /// ```
+    pub fn reload_paragraphs(&mut self, mut paragraphs: Vec<Paragraph>) -> bool {
+        let matches = paragraphs.len() == self.paragraphs.len()
+            && paragraphs
+                .iter()
+                .zip(self.paragraphs.iter())
+                .all(|(paragraph, old)| paragraph.matches_layout(old));
+
+        if matches {
+            paragraphs
+                .iter_mut()
+                .zip(self.paragraphs.iter())
+                .for_each(|(paragraph, old)| paragraph.copy_progress(old));
+            self.paragraphs = paragraphs;
+        } else {
+            self.set_paragraphs(paragraphs);
+        }
+        matches
+    }
+    /// Ends the dialogue, leaving it with no current paragraph
+    pub fn stop(&mut self) {
+        self.branch.clear();
+        self.current_paragraph = self.paragraphs.len();
+    }
+    /// Goes back to the first paragraph
+    pub fn restart(&mut self) {
+        self.branch.clear();
+        self.current_paragraph = 0;
+        self.reset_current_paragraph();
+    }
+    fn reset_current_paragraph(&mut self) {
+        self.visits += 1;
+        if let Some(paragraph) = self.get_current_paragraph_mut() {
+            paragraph.reset();
+        }
+    }
+    /// The index of the paragraph with the given name
+    pub fn position_of(&self, name: &str) -> Option<usize> {
+        self.paragraphs
+            .iter()
+            .position(|paragraph| paragraph.name() == Some(name))
+    }
+    pub fn get_paragraph(&self, name: &str) -> Option<&Paragraph> {
+        self.position_of(name)
+            .and_then(|index| self.paragraphs.get(index))
+    }
+    pub fn get_paragraph_mut(&mut self, name: &str) -> Option<&mut Paragraph> {
+        self.position_of(name)
+            .and_then(|index| self.paragraphs.get_mut(index))
     }
-    pub fn paragraphs(&self) -> &VecDeque<Paragraph> {
+    /// The choice the player is currently being asked to make, if any
+    pub fn get_current_choice(&self) -> Option<&Choice> {
+        self.get_current_paragraph()
+            .filter(|paragraph| paragraph.awaiting_choice())
+            .and_then(|paragraph| paragraph.get_choice())
+    }
+    pub fn get_current_choice_mut(&mut self) -> Option<&mut Choice> {
+        self.get_current_paragraph_mut()
+            .filter(|paragraph| paragraph.awaiting_choice())
+            .and_then(|paragraph| paragraph.get_choice_mut())
+    }
+    /// Picks an option of the current choice, moving on to the paragraphs of that option before carrying on with the rest of the dialogue
+    /// ```
+    /// # use bevy_dialogue_system::prelude::*;
+    /// let mut dialogue = Dialogue::new().with_paragraphs(vec![
+    ///     Paragraph::new().with_choice(Choice::new().with_options(vec![
+    ///         ChoiceOption::new("Left").push_paragraph(
+    ///             Paragraph::new().with_sentences(vec![Sentence::new().with_text("You went left")]),
+    ///         ),
+    ///         ChoiceOption::new("Right").push_paragraph(
+    ///             Paragraph::new().with_sentences(vec![Sentence::new().with_text("You went right")]),
+    ///         ),
+    ///     ])),
+    ///     Paragraph::new().with_sentences(vec![Sentence::new().with_text("The end")]),
+    /// ]);
+    ///
+    /// dialogue.select_choice(1);
+    ///
+    /// let current = dialogue.get_current_paragraph().unwrap();
+    /// assert_eq!(current.sentences()[0].text(), "You went right");
+    /// assert!(dialogue.in_branch());
+    ///
+    /// dialogue.advance_paragraph();
+    ///
+    /// let current = dialogue.get_current_paragraph().unwrap();
+    /// assert_eq!(current.sentences()[0].text(), "The end");
+    /// ```
+    pub fn select_choice(&mut self, index: usize) {
+        let Some(option) = self
+            .get_current_choice()
+            .and_then(|choice| choice.options().get(index))
+        else {
+            return;
+        };
+        let paragraphs = option.paragraphs().clone();
+        let jump = option.get_jump().cloned();
+        let origin = format!("{}>{}", self.paragraph_location(), index);
+
+        if self.branch.pop_front().is_none() {
+            self.current_paragraph = (self.current_paragraph + 1).min(self.paragraphs.len());
+        }
+        if let Some(jump) = jump {
+            if !self.goto(&jump) {
+                warn!("Could not jump to missing paragraph {jump:?}, continuing on instead");
+            }
+        }
+        paragraphs
+            .into_iter()
+            .enumerate()
+            .rev()
+            .for_each(|(position, paragraph)| {
+                self.branch
+                    .push_front((format!("{}.{}", origin, position), paragraph))
+            });
+        self.reset_current_paragraph();
+    }
+
+    /// Every paragraph of the dialogue, including the ones already shown
+    ///
+    /// This used to be a `VecDeque` that shown paragraphs were popped from, use [`Dialogue::current_index`] to tell
+    /// how far along the dialogue is
+    pub fn paragraphs(&self) -> &Vec<Paragraph> {
         &self.paragraphs
     }
-    pub fn paragraphs_mut(&mut self) -> &mut VecDeque<Paragraph> {
+    pub fn paragraphs_mut(&mut self) -> &mut Vec<Paragraph> {
         &mut self.paragraphs
     }
+    /// How many paragraphs are left to show, counting the current one and those of a picked option
+    /// ```
+    /// # use bevy_dialogue_system::prelude::*;
+    /// let mut dialogue = Dialogue::new().with_paragraphs(vec![
+    ///     Paragraph::new().with_sentences(vec![Sentence::new().with_text("Hello")]),
+    ///     Paragraph::new().with_sentences(vec![Sentence::new().with_text("there")]),
+    /// ]);
+    /// assert_eq!(dialogue.len(), 2);
+    ///
+    /// dialogue.advance_paragraph();
+    /// dialogue.advance_paragraph();
+    /// assert!(dialogue.is_empty());
+    /// assert_eq!(dialogue.paragraphs().len(), 2);
+    /// ```
     pub fn len(&self) -> usize {
-        self.paragraphs.len()
+        // Picking an option already moves past the paragraph holding the choice
+        self.branch.len() + self.paragraphs.len().saturating_sub(self.current_paragraph)
     }
+    /// Whether there are no paragraphs left to show, the same as [`Dialogue::is_finished`]
     pub fn is_empty(&self) -> bool {
-        self.paragraphs.is_empty()
+        self.is_finished()
+    }
+    /// Whether there are no paragraphs left to show
+    pub fn is_finished(&self) -> bool {
+        self.get_current_paragraph().is_none()
     }
 
+    /// The keys that advance the dialogue and confirm choices, leaving its other [`DialogueBindings`] as they are
     /// ```rust
     /// # use bevy_dialogue_system::prelude::*;
     /// # use bevy::prelude::*;
@@ -79,7 +427,7 @@ impl Dialogue {
     /// assert_eq!(dialogue.skip_keys().cloned().collect_vec(), NEW_KEYS.to_vec());
     /// ```
     pub fn with_keys(mut self, keys: Vec<KeyCode>) -> Self {
-        self.skip_keys = keys;
+        self.set_keys(keys);
         self
     }
     /// ```rust
@@ -94,10 +442,12 @@ impl Dialogue {
     /// assert_eq!(dialogue.skip_keys().last().cloned(), Some(KeyCode::KeyA));
     /// ```
     pub fn push_key(mut self, key: KeyCode) -> Self {
-        self.skip_keys.push(key);
+        self.add_key(key);
         self
     }
 
+    /// Replaces the keys that advance and confirm choices, unbinding them from every other action so that one
+    /// press doesn't do two things
     /// ```rust
     /// # use bevy_dialogue_system::prelude::*;
     /// # use bevy::prelude::*;
@@ -112,8 +462,16 @@ impl Dialogue {
     /// assert_eq!(dialogue.skip_keys().cloned().collect_vec(), NEW_KEYS.to_vec());
     /// ```
     pub fn set_keys(&mut self, keys: Vec<KeyCode>) {
-        self.skip_keys = keys;
+        keys.iter().for_each(|key| self.bindings.unbind_all(*key));
+        [DialogueAction::Advance, DialogueAction::ChoiceConfirm]
+            .into_iter()
+            .for_each(|action| {
+                let bindings = self.bindings.get_mut(action);
+                bindings.retain(|binding| !matches!(binding, InputBinding::Key(_)));
+                bindings.extend(keys.iter().copied().map(InputBinding::Key));
+            });
     }
+    /// Adds a key that advances and confirms choices, unbinding it from every other action
     /// ```rust
     /// # use bevy_dialogue_system::prelude::*;
     /// # use bevy::prelude::*;
@@ -125,9 +483,14 @@ impl Dialogue {
     ///
     /// assert_eq!(dialogue.skip_keys().last().cloned(), Some(KeyCode::KeyB));
     ///
+    /// dialogue.add_key(KeyCode::Tab);
+    ///
+    /// assert!(dialogue.bindings().get(DialogueAction::SkipAll).is_empty());
     /// ```
     pub fn add_key(&mut self, key: KeyCode) {
-        self.skip_keys.push(key);
+        self.bindings.unbind_all(key);
+        self.bindings.bind(DialogueAction::Advance, key);
+        self.bindings.bind(DialogueAction::ChoiceConfirm, key);
     }
 
     /// ```rust
@@ -140,7 +503,13 @@ impl Dialogue {
     /// assert_eq!(dialogue.skip_keys().cloned().collect_vec(), vec![KeyCode::Space, KeyCode::Enter]);
     /// ```
     pub fn skip_keys(&self) -> impl Iterator<Item = &KeyCode> {
-        self.skip_keys.iter()
+        self.bindings
+            .get(DialogueAction::Advance)
+            .iter()
+            .filter_map(|binding| match binding {
+                InputBinding::Key(key) => Some(key),
+                _ => None,
+            })
     }
     /// ```rust
     /// # use bevy_dialogue_system::prelude::*;
@@ -160,7 +529,28 @@ impl Dialogue {
     /// assert_eq!(dialogue.skip_keys().cloned().collect_vec(), vec![KeyCode::KeyZ, KeyCode::Enter]);
     /// ```
     pub fn mut_skip_keys(&mut self) -> impl Iterator<Item = &mut KeyCode> {
-        self.skip_keys.iter_mut()
+        self.bindings
+            .get_mut(DialogueAction::Advance)
+            .iter_mut()
+            .filter_map(|binding| match binding {
+                InputBinding::Key(key) => Some(key),
+                _ => None,
+            })
+    }
+
+    /// Which inputs trigger which actions, see [`DialogueBindings`]
+    pub fn with_bindings(mut self, bindings: DialogueBindings) -> Self {
+        self.bindings = bindings;
+        self
+    }
+    pub fn set_bindings(&mut self, bindings: DialogueBindings) {
+        self.bindings = bindings;
+    }
+    pub fn bindings(&self) -> &DialogueBindings {
+        &self.bindings
+    }
+    pub fn bindings_mut(&mut self) -> &mut DialogueBindings {
+        &mut self.bindings
     }
 
     pub fn with_hide_on_empty(mut self, hide: bool) -> Self {
@@ -173,4 +563,365 @@ impl Dialogue {
     pub fn hide_on_empty(&self) -> bool {
         self.hide_on_empty
     }
+
+    /// How long the typewriter waits after punctuation in this dialogue
+    pub fn with_pauses(mut self, pauses: TypeWriterPauses) -> Self {
+        self.pauses = pauses;
+        self
+    }
+    pub fn set_pauses(&mut self, pauses: TypeWriterPauses) {
+        self.pauses = pauses;
+    }
+    pub fn pauses(&self) -> &TypeWriterPauses {
+        &self.pauses
+    }
+    pub fn pauses_mut(&mut self) -> &mut TypeWriterPauses {
+        &mut self.pauses
+    }
+    /// Whether the dialogue races through its lines, finishing typewriters at once and moving on every
+    /// [`Dialogue::skip_interval`] seconds
+    ///
+    /// The plugin sets this every frame from whether [`DialogueAction::FastForward`] is held
+    pub fn set_fast_forward(&mut self, fast_forward: bool) {
+        self.fast_forward = fast_forward;
+    }
+    pub fn is_fast_forwarding(&self) -> bool {
+        self.fast_forward
+    }
+
+    /// Races through the dialogue like fast-forwarding, until it reaches a choice, the end or, when only skipping
+    /// seen lines, a line that hasn't been seen. [`DialogueAction::SkipAll`] toggles this and advancing stops it
+    /// ```
+    /// # use bevy_dialogue_system::prelude::*;
+    /// # use bevy::prelude::*;
+    /// let mut app = App::new();
+    /// app.add_plugins((MinimalPlugins, AssetPlugin::default(), bevy::input::InputPlugin, DialoguePlugin));
+    /// # app.finish();
+    /// # app.cleanup();
+    ///
+    /// let mut dialogue = Dialogue::new().with_skip_interval(0.).with_paragraphs(vec![
+    ///     Paragraph::new().with_sentences(vec![Sentence::new().with_text("One").create_typewriter()]),
+    ///     Paragraph::new().with_sentences(vec![Sentence::new().with_text("Two").create_typewriter()]),
+    ///     Paragraph::new()
+    ///         .with_sentences(vec![Sentence::new().with_text("Pick one")])
+    ///         .with_choice(Choice::new().with_options(vec![ChoiceOption::new("This")])),
+    /// ]);
+    /// dialogue.start_skipping();
+    /// let dialogue = app.world.spawn(dialogue).id();
+    ///
+    /// (0..10).for_each(|_| app.update());
+    ///
+    /// let dialogue = app.world.get::<Dialogue>(dialogue).unwrap();
+    /// assert_eq!(dialogue.get_current_paragraph().unwrap().sentences()[0].text(), "Pick one");
+    /// assert!(!dialogue.is_skipping());
+    /// assert!(dialogue.has_seen("0:0") && dialogue.has_seen("1:0"));
+    /// ```
+    pub fn start_skipping(&mut self) {
+        self.skipping = true;
+    }
+    pub fn stop_skipping(&mut self) {
+        self.skipping = false;
+    }
+    pub fn is_skipping(&self) -> bool {
+        self.skipping
+    }
+
+    /// How many seconds fast-forwarding and skipping show each line for
+    pub fn with_skip_interval(mut self, seconds: f32) -> Self {
+        self.set_skip_interval(seconds);
+        self
+    }
+    pub fn set_skip_interval(&mut self, seconds: f32) {
+        self.skip_interval = seconds.max(0.);
+    }
+    pub fn skip_interval(&self) -> f32 {
+        self.skip_interval
+    }
+
+    /// Whether fast-forwarding and skipping stop at lines that haven't been seen before
+    pub fn with_skip_seen_only(mut self, seen_only: bool) -> Self {
+        self.skip_seen_only = seen_only;
+        self
+    }
+    pub fn set_skip_seen_only(&mut self, seen_only: bool) {
+        self.skip_seen_only = seen_only;
+    }
+    pub fn skips_seen_only(&self) -> bool {
+        self.skip_seen_only
+    }
+
+    /// Whether the line has been read to the end before, which is kept across restarts
+    ///
+    /// Lines are told apart by their [`Sentence::line_id`] or else by where they are, see [`Dialogue::current_line`],
+    /// so the same text in two places is two different lines
+    pub fn has_seen(&self, line: &str) -> bool {
+        self.seen.contains(line)
+    }
+    pub fn mark_seen(&mut self, line: impl ToString) {
+        self.seen.insert(line.to_string());
+    }
+    /// What the current sentence is remembered as once seen: its [`Sentence::line_id`] if it has one, otherwise the
+    /// name of its paragraph, or the paragraph's index when it has no name, and the index of the sentence, such as
+    /// `"intro:2"` or `"0:1"`
+    ///
+    /// Unnamed paragraphs of a picked option are placed after the paragraph and option they come from, such as
+    /// `"3>1.0:0"` for the first sentence of the first paragraph of option 1 of paragraph 3
+    /// ```
+    /// # use bevy_dialogue_system::prelude::*;
+    /// let mut dialogue = Dialogue::new().with_paragraphs(vec![
+    ///     Paragraph::new().with_sentences(vec![Sentence::new().with_text("..."), Sentence::new().with_text("Yes.")]),
+    ///     Paragraph::new()
+    ///         .with_name("end")
+    ///         .with_sentences(vec![Sentence::new().with_text("Yes.").with_line_id("guard_yes")]),
+    /// ]);
+    /// assert_eq!(dialogue.current_line().as_deref(), Some("0:0"));
+    ///
+    /// dialogue.goto("end");
+    /// assert_eq!(dialogue.current_line().as_deref(), Some("guard_yes"));
+    /// ```
+    pub fn current_line(&self) -> Option<String> {
+        let paragraph = self.get_current_paragraph()?;
+        let sentence = paragraph.get_current_sentence()?;
+
+        Some(match sentence.line_id() {
+            Some(id) => id.to_string(),
+            None => format!(
+                "{}:{}",
+                self.paragraph_location(),
+                paragraph.current_sentence_index()
+            ),
+        })
+    }
+    fn paragraph_location(&self) -> String {
+        let name = self
+            .get_current_paragraph()
+            .and_then(|paragraph| paragraph.name());
+        match (name, self.branch.front()) {
+            (Some(name), _) => name.to_string(),
+            (None, Some((location, _))) => location.clone(),
+            (None, None) => self.current_paragraph.to_string(),
+        }
+    }
+    /// Every line seen so far, to be kept in a save game and given back with [`Dialogue::set_seen`]
+    pub fn seen(&self) -> &HashSet<String> {
+        &self.seen
+    }
+    pub fn set_seen(&mut self, seen: HashSet<String>) {
+        self.seen = seen;
+    }
+
+    /// Moves fast-forwarding and skipping along `amount` seconds, returning whether it's time to advance
+    pub(crate) fn update_skip(&mut self, amount: f32) -> bool {
+        if self.is_waiting() {
+            return false;
+        }
+        if !self.fast_forward && !self.skipping {
+            self.skip_timer = 0.;
+            return false;
+        }
+        let Some(paragraph) = self.get_current_paragraph() else {
+            self.skipping = false;
+            return false;
+        };
+        if !paragraph.is_started() {
+            return false;
+        }
+
+        let unread = paragraph
+            .get_current_sentence()
+            .is_some_and(|sentence| !sentence.is_skipped())
+            && !self.current_line().is_some_and(|line| self.has_seen(&line));
+        let waits_for_input = paragraph.get_current_sentence().is_some_and(|sentence| {
+            !sentence.is_skipped() && sentence.get_wait() == Some(&DialogueWait::Input)
+        });
+        if paragraph.awaiting_choice() || (self.skip_seen_only && unread) || waits_for_input {
+            self.skipping = false;
+            return false;
+        }
+
+        if let Some(sentence) = self
+            .get_current_paragraph_mut()
+            .and_then(|paragraph| paragraph.get_current_sentence_mut())
+        {
+            sentence.mut_typewriter().finish();
+        }
+
+        self.skip_timer += amount;
+        if self.skip_timer < self.skip_interval {
+            return false;
+        }
+        self.skip_timer = 0.;
+        true
+    }
+
+    /// Whether the dialogue moves on by itself once each line has been typed out and given time to be read,
+    /// [`DialogueAction::ToggleAuto`] turns this on and off and advancing stops it
+    /// ```
+    /// # use bevy_dialogue_system::prelude::*;
+    /// # use bevy::prelude::*;
+    /// let mut app = App::new();
+    /// app.add_plugins((MinimalPlugins, AssetPlugin::default(), bevy::input::InputPlugin, DialoguePlugin));
+    /// # app.finish();
+    /// # app.cleanup();
+    ///
+    /// let dialogue = Dialogue::new()
+    ///     .with_auto(true)
+    ///     .with_auto_advance(AutoAdvance::new(0., 0.))
+    ///     .with_paragraphs(vec![
+    ///         Paragraph::new().with_sentences(vec![Sentence::new().with_text("One"), Sentence::new().with_text("Two")]),
+    ///         Paragraph::new()
+    ///             .with_sentences(vec![Sentence::new().with_text("Pick one")])
+    ///             .with_choice(Choice::new().with_options(vec![ChoiceOption::new("This")])),
+    ///     ]);
+    /// let dialogue = app.world.spawn(dialogue).id();
+    ///
+    /// (0..10).for_each(|_| app.update());
+    ///
+    /// let dialogue = app.world.get::<Dialogue>(dialogue).unwrap();
+    /// assert_eq!(dialogue.get_current_paragraph().unwrap().sentences()[0].text(), "Pick one");
+    /// assert!(dialogue.is_auto());
+    /// ```
+    pub fn with_auto(mut self, auto: bool) -> Self {
+        self.auto = auto;
+        self
+    }
+    pub fn set_auto(&mut self, auto: bool) {
+        self.auto = auto;
+        self.auto_timer = 0.;
+    }
+    pub fn is_auto(&self) -> bool {
+        self.auto
+    }
+
+    pub fn with_auto_advance(mut self, auto_advance: AutoAdvance) -> Self {
+        self.auto_advance = auto_advance;
+        self
+    }
+    pub fn set_auto_advance(&mut self, auto_advance: AutoAdvance) {
+        self.auto_advance = auto_advance;
+    }
+    pub fn auto_advance(&self) -> &AutoAdvance {
+        &self.auto_advance
+    }
+
+    /// Moves auto mode along `amount` seconds, returning whether it's time to advance
+    pub(crate) fn update_auto(&mut self, amount: f32) -> bool {
+        let delay = self
+            .get_current_paragraph()
+            .filter(|paragraph| {
+                self.auto
+                    && !self.is_waiting()
+                    && self.current_wait().is_none()
+                    && paragraph.is_started()
+                    && !paragraph.awaiting_choice()
+                    && paragraph.all_characters_displayed()
+            })
+            .map(|paragraph| match paragraph.get_current_sentence() {
+                Some(sentence) if !sentence.is_skipped() => {
+                    self.auto_advance.delay(sentence.text())
+                }
+                _ => 0.,
+            });
+
+        let Some(delay) = delay else {
+            self.auto_timer = 0.;
+            return false;
+        };
+
+        self.auto_timer += amount;
+        if self.auto_timer < delay {
+            return false;
+        }
+        self.auto_timer = 0.;
+        true
+    }
+    /// Where the dialogue is now, `None` once it has ended
+    pub(crate) fn progress(&self) -> Option<Progress> {
+        let paragraph = self.get_current_paragraph()?;
+
+        Some(Progress {
+            visits: self.visits,
+            paragraph: self.current_paragraph,
+            name: paragraph.name().map(str::to_string),
+            in_branch: self.in_branch(),
+            sentence: paragraph.current_sentence_index(),
+            typed: paragraph.all_characters_displayed(),
+        })
+    }
+    /// Remembers where the dialogue was when its events were sent, returning where it was last time
+    pub(crate) fn report(&mut self, progress: Option<Progress>) -> Option<Progress> {
+        std::mem::replace(&mut self.reported, progress)
+    }
+
+    /// Starts the wait before skipping and auto mode move on again, after the dialogue has advanced
+    pub(crate) fn reset_timers(&mut self) {
+        self.skip_timer = 0.;
+        self.auto_timer = 0.;
+        self.wait_timer = 0.;
+    }
+
+    /// The wait of the current sentence once it has been typed out, see [`Sentence::with_wait`]
+    fn current_wait(&self) -> Option<DialogueWait> {
+        self.get_current_paragraph()
+            .filter(|paragraph| {
+                paragraph.is_started()
+                    && !paragraph.awaiting_choice()
+                    && paragraph.all_characters_displayed()
+            })
+            .and_then(Paragraph::get_current_sentence)
+            .filter(|sentence| !sentence.is_skipped())
+            .and_then(Sentence::get_wait)
+            .copied()
+    }
+    /// Whether the current sentence is holding a timed wait, during which the player can't advance
+    pub fn is_pausing(&self) -> bool {
+        self.current_wait()
+            .is_some_and(|wait| wait.seconds().is_some())
+    }
+    /// Moves a timed wait along `amount` seconds, returning whether it's time to advance
+    pub(crate) fn update_wait(&mut self, amount: f32) -> bool {
+        let Some(seconds) = self
+            .current_wait()
+            .and_then(|wait| wait.seconds())
+            .filter(|_| !self.is_waiting())
+        else {
+            self.wait_timer = 0.;
+            return false;
+        };
+
+        self.wait_timer += amount;
+        if self.wait_timer < seconds {
+            return false;
+        }
+        self.wait_timer = 0.;
+        true
+    }
+
+    /// Whether the dialogue is held by a blocking action, see [`Sentence::with_blocking_action`]
+    pub fn is_waiting(&self) -> bool {
+        self.waiting.is_some()
+    }
+    pub(crate) fn block(&mut self, then: AfterWait) {
+        self.waiting = Some(then);
+    }
+    /// Stops waiting, returning what to do now if the dialogue was waiting
+    pub(crate) fn resume(&mut self) -> Option<AfterWait> {
+        self.reset_timers();
+        self.waiting.take()
+    }
+
+    /// Advances the typewriter of the current paragraph, waiting after punctuation as set by [`Dialogue::with_pauses`]
+    pub fn advance_typewriter(&mut self, amount: f32, settings: &TypeWriterSettings) {
+        if self.is_waiting() {
+            return;
+        }
+        let paragraph = match self.branch.front_mut() {
+            Some((_, paragraph)) => Some(paragraph),
+            None => self.paragraphs.get_mut(self.current_paragraph),
+        };
+
+        if let Some(paragraph) = paragraph {
+            paragraph.advance_typewriter(amount, settings, &self.pauses);
+        }
+    }
 }
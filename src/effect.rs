@@ -0,0 +1,99 @@
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// An animation played on text for as long as it is on screen, set on a whole [`Sentence`] with
+/// [`Sentence::with_effect`] or on part of one with `[wave]`, `[shake]`, `[rainbow]` or `[pulse]` markup
+/// ```
+/// # use bevy_dialogue_system::prelude::*;
+/// # use bevy::prelude::*;
+/// let sentence = Sentence::new().with_markup("I'm [shake]not[/shake] scared");
+///
+/// assert_eq!(sentence.spans()[0].effects, vec![TextEffect::Shake]);
+///
+/// let sections = sentence.animate(1., &TextEffectSettings::default());
+/// assert_eq!(sections.len(), 5);
+/// assert_eq!(sections[1].0.value, "n");
+/// assert_eq!(sections[0].1, Vec2::ZERO);
+///
+/// let still = sentence.animate(1., &TextEffectSettings::reduced_motion());
+/// assert!(still.iter().all(|(_, offset)| *offset == Vec2::ZERO));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TextEffect {
+    /// Characters bob up and down one after another
+    Wave,
+    /// Characters jitter around, as if scared
+    Shake,
+    /// Characters cycle through the colors of the rainbow
+    Rainbow,
+    /// The text fades in and out a little for emphasis
+    Pulse,
+}
+impl TextEffect {
+    pub(crate) fn parse(tag: &str) -> Option<Self> {
+        Some(match tag {
+            "wave" => Self::Wave,
+            "shake" => Self::Shake,
+            "rainbow" => Self::Rainbow,
+            "pulse" => Self::Pulse,
+            _ => return None,
+        })
+    }
+    pub(crate) fn name(&self) -> &str {
+        match self {
+            Self::Wave => "wave",
+            Self::Shake => "shake",
+            Self::Rainbow => "rainbow",
+            Self::Pulse => "pulse",
+        }
+    }
+
+    /// Animates the `character`th character of a sentence, its offset being in character heights
+    pub(crate) fn apply(
+        &self,
+        character: usize,
+        time: f32,
+        settings: &TextEffectSettings,
+        color: &mut Color,
+        offset: &mut Vec2,
+    ) {
+        let character = character as f32;
+
+        match self {
+            _ if settings.reduced_motion && *self != Self::Rainbow => {}
+            Self::Wave => offset.y -= 0.15 * (time * 6. + character * 0.6).sin(),
+            Self::Shake => {
+                let step = (time * 20.).floor();
+                *offset += Vec2::new(jitter(character, step), jitter(character + 0.5, step)) * 0.06;
+            }
+            Self::Rainbow => {
+                // Holding still for reduced motion, each character keeping its own color
+                let time = if settings.reduced_motion { 0. } else { time };
+                let hue = (time * 120. + character * 25.).rem_euclid(360.);
+                *color = Color::hsla(hue, 0.8, 0.6, color.a());
+            }
+            Self::Pulse => {
+                color.set_a(color.a() * (0.8 + 0.2 * (time * 4.).sin()));
+            }
+        }
+    }
+}
+
+/// A repeatable random number from -1 to 1
+fn jitter(character: f32, step: f32) -> f32 {
+    ((character * 12.9898 + step * 78.233).sin() * 43758.547).fract() * 2. - 1.
+}
+
+/// How [`TextEffect`]s and the typewriter's [`TypeWriterReveal`] play, insert it as a resource to change it
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Default)]
+pub struct TextEffectSettings {
+    /// Keeps text from moving, so waves, shakes, pulses, slides and pops stay still and rainbows stop cycling
+    pub reduced_motion: bool,
+}
+impl TextEffectSettings {
+    pub fn reduced_motion() -> Self {
+        Self {
+            reduced_motion: true,
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use crate::prelude::*;
+
+/// Sent when a dialogue shows its first paragraph, again after restarting once it has ended
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct DialogueStarted {
+    pub dialogue: Entity,
+}
+
+/// Sent when a dialogue moves on to a paragraph, `paragraph` being [`Dialogue::current_index`]
+/// and `in_branch` whether it comes from a picked option instead
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct ParagraphStarted {
+    pub dialogue: Entity,
+    pub paragraph: usize,
+    pub name: Option<String>,
+    pub in_branch: bool,
+}
+
+/// Sent when a dialogue moves on from a paragraph, see [`ParagraphStarted`]
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct ParagraphFinished {
+    pub dialogue: Entity,
+    pub paragraph: usize,
+    pub name: Option<String>,
+    pub in_branch: bool,
+}
+
+/// Sent when a sentence starts being shown, `sentence` being its index in the paragraph
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct SentenceStarted {
+    pub dialogue: Entity,
+    pub paragraph: usize,
+    pub sentence: usize,
+}
+
+/// Sent once every character of a sentence is showing, whether the typewriter got there or was skipped
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct SentenceFinishedTyping {
+    pub dialogue: Entity,
+    pub paragraph: usize,
+    pub sentence: usize,
+}
+
+/// Sent when a dialogue runs out of paragraphs or is stopped
+/// ```
+/// # use bevy_dialogue_system::prelude::*;
+/// # use bevy::prelude::*;
+/// let mut app = App::new();
+/// app.add_plugins((MinimalPlugins, AssetPlugin::default(), bevy::input::InputPlugin, DialoguePlugin));
+/// # app.finish();
+/// # app.cleanup();
+///
+/// let dialogue = app
+///     .world
+///     .spawn(Dialogue::new().with_paragraphs(vec![
+///         Paragraph::new().with_sentences(vec![Sentence::new().with_text("Hello")]),
+///     ]))
+///     .id();
+/// app.update();
+///
+/// let started = app.world.resource::<Events<ParagraphStarted>>();
+/// assert_eq!(started.get_reader().read(started).next().unwrap().paragraph, 0);
+///
+/// app.world.send_event(DialogueInput::to(dialogue, DialogueAction::Advance));
+/// app.update();
+///
+/// let ended = app.world.resource::<Events<DialogueEnded>>();
+/// assert_eq!(ended.get_reader().read(ended).next(), Some(&DialogueEnded { dialogue }));
+/// ```
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct DialogueEnded {
+    pub dialogue: Entity,
+}
+
+/// Lets a dialogue waiting on a blocking action go on, see [`Sentence::with_blocking_action`]
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct ResumeDialogue(pub Entity);
+
+/// Where a dialogue was when its events were last sent
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Progress {
+    /// Counts every time the dialogue moves to a paragraph, so going back to the same one is noticed
+    pub visits: usize,
+    pub paragraph: usize,
+    pub name: Option<String>,
+    pub in_branch: bool,
+    pub sentence: usize,
+    pub typed: bool,
+}
@@ -0,0 +1,1034 @@
+use crate::prelude::*;
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    utils::BoxedFuture,
+};
+use serde_json::{Map, Value};
+use std::sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// Converts compiled [ink](https://www.inklestudios.com/ink) stories, the `.ink.json` files written by Inky and inklecate,
+/// into paragraphs
+///
+/// Knots, stitches and gathers become paragraphs named after their path, choices become a [`Choice`] whose options jump
+/// to their content and global variables are kept in [`DialogueVariables`]. Calling an external function runs the action
+/// added for it with [`InkImporter::with_external`] once the player moves past the line before the call, or else the
+/// command of the same name in [`DialogueCommands`], which is given the call's arguments. Arguments can be plain values
+/// or global variables, variables being read when the command runs.
+///
+/// Anything that needs ink's own runtime, like read counts, sequences, functions, tunnels, threads, lists or printing
+/// variables, is reported as an [`InkDiagnostic`].
+/// ```
+/// # use bevy_dialogue_system::prelude::*;
+/// // VAR gold = 0
+/// // Hello!
+/// // * [Take the coin]
+/// //     ~ gold = gold + 1
+/// //     You pick it up.
+/// // * [Leave it]
+/// // - -> END
+/// let story = r##"{"inkVersion":21,"root":[["^Hello!","\n",
+///     "ev","str","^Take the coin","/str","/ev",{"*":"0.c-0","flg":20},
+///     "ev","str","^Leave it","/str","/ev",{"*":"0.c-1","flg":20},
+///     {"c-0":["\n","ev",{"VAR?":"gold"},1,"+",{"VAR=":"gold","re":true},"/ev","^You pick it up.","\n",{"->":"0.g-0"},{"#f":5}],
+///     "c-1":["\n",{"->":"0.g-0"},{"#f":5}],
+///     "g-0":["end",null]}],
+///     "done",{"global decl":["ev",0,{"VAR=":"gold"},"/ev","end",null]}],"listDefs":{}}"##;
+///
+/// let paragraphs = InkImporter::new().import(story, "coin.ink.json").unwrap();
+/// let mut dialogue = Dialogue::new().with_paragraphs(paragraphs);
+/// let mut variables = DialogueVariables::new();
+///
+/// dialogue.start_paragraph(&mut variables);
+/// let paragraph = dialogue.get_current_paragraph_mut().unwrap();
+/// assert_eq!(paragraph.sentences()[0].text(), "Hello!");
+/// assert_eq!(paragraph.get_choice().unwrap().options()[0].label().text(), "Take the coin");
+///
+/// paragraph.update_typewriter(10., &TypeWriterSettings::default());
+/// dialogue.select_choice(0);
+/// dialogue.start_paragraph(&mut variables);
+/// assert_eq!(dialogue.get_current_paragraph().unwrap().sentences()[0].text(), "You pick it up.");
+/// assert_eq!(variables.get("gold"), Some(&DialogueValue::Int(1)));
+///
+/// let counted = r#"{"inkVersion":21,"root":[["ev",{"CNT?":".^"},"pop","/ev","done",null],"done",null]}"#;
+/// let errors = InkImporter::new().import(counted, "count.ink.json").unwrap_err();
+/// assert_eq!(errors.diagnostics()[0].message, "read counts aren't supported");
+/// ```
+///
+/// Adding it as a resource before the app runs lets `.ink.json` assets call the same external functions. Clones share
+/// their externals, so ones added to the resource once the app is running are still known to the loader.
+#[derive(Resource, Default, Clone)]
+pub struct InkImporter {
+    externals: Arc<RwLock<HashMap<String, SentenceAction>>>,
+}
+impl InkImporter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_external(mut self, name: impl ToString, action: fn(&mut World)) -> Self {
+        self.add_external(name, action);
+        self
+    }
+    /// Calls to an external function added this way can't take arguments, add a command to [`DialogueCommands`]
+    /// for those instead
+    pub fn with_external_action(mut self, name: impl ToString, action: SentenceAction) -> Self {
+        self.add_external_action(name, action);
+        self
+    }
+    pub fn add_external(&mut self, name: impl ToString, action: fn(&mut World)) {
+        self.add_external_action(name, SentenceAction::World(action));
+    }
+    pub fn add_external_action(&mut self, name: impl ToString, action: SentenceAction) {
+        self.write().insert(name.to_string(), action);
+    }
+    pub fn remove_external(&mut self, name: &str) {
+        self.write().remove(name);
+    }
+    pub fn get_external(&self, name: &str) -> Option<SentenceAction> {
+        self.read().get(name).cloned()
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, HashMap<String, SentenceAction>> {
+        self.externals
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+    fn write(&self) -> RwLockWriteGuard<'_, HashMap<String, SentenceAction>> {
+        self.externals
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub fn import(&self, json: &str, file: &str) -> Result<Vec<Paragraph>, InkError> {
+        self.import_with(json, file, &DialogueCommands::default())
+    }
+    /// Imports a story whose external function calls can also run the given commands
+    /// ```
+    /// # use bevy_dialogue_system::prelude::*;
+    /// # use bevy::prelude::*;
+    /// #[derive(Resource, Default)]
+    /// struct Inventory(Vec<(String, i64)>);
+    ///
+    /// fn give_item(In(input): In<CommandInput>, mut inventory: ResMut<Inventory>) {
+    ///     if let [DialogueValue::String(item), DialogueValue::Int(count)] = input.arguments.as_slice() {
+    ///         inventory.0.push((item.clone(), *count));
+    ///     }
+    /// }
+    ///
+    /// // EXTERNAL give_item(item, count)
+    /// // Take these.
+    /// // ~ give_item("arrow", gold)
+    /// let story = r##"{"inkVersion":21,"root":[["^Take these.","\n",
+    ///     "ev","str","^arrow","/str",{"VAR?":"gold"},{"x()":"give_item","exArgs":2},"pop","/ev","done",null],
+    ///     "done",null],"listDefs":{}}"##;
+    ///
+    /// let errors = InkImporter::new().import(story, "arrows.ink.json").unwrap_err();
+    /// assert_eq!(errors.diagnostics()[0].message, "no external function or command named \"give_item\" was added");
+    ///
+    /// let commands = DialogueCommands::new()
+    ///     .with("give_item", [CommandArgument::String, CommandArgument::Int], give_item);
+    /// let paragraphs = InkImporter::new().import_with(story, "arrows.ink.json", &commands).unwrap();
+    ///
+    /// let mut app = App::new();
+    /// app.add_plugins((MinimalPlugins, AssetPlugin::default(), bevy::input::InputPlugin, DialoguePlugin))
+    ///     .insert_resource(commands)
+    ///     .insert_resource(DialogueVariables::new().with("gold", 3))
+    ///     .init_resource::<Inventory>();
+    /// # app.finish();
+    /// # app.cleanup();
+    ///
+    /// let dialogue = app.world.spawn(Dialogue::new().with_paragraphs(paragraphs)).id();
+    /// app.update();
+    /// for _ in 0..2 {
+    ///     app.world.send_event(DialogueInput::to(dialogue, DialogueAction::Advance));
+    ///     app.update();
+    /// }
+    /// assert_eq!(app.world.resource::<Inventory>().0, vec![("arrow".to_string(), 3)]);
+    /// ```
+    pub fn import_with(
+        &self,
+        json: &str,
+        file: &str,
+        commands: &DialogueCommands,
+    ) -> Result<Vec<Paragraph>, InkError> {
+        let error = |message: String| InkError {
+            diagnostics: vec![InkDiagnostic::new(file, "", message)],
+        };
+
+        let json: Value = serde_json::from_str(json.trim_start_matches('\u{feff}'))
+            .map_err(|json_error| error(format!("invalid JSON: {}", json_error)))?;
+        let story = json
+            .get("root")
+            .and_then(Story::new)
+            .ok_or_else(|| error("not a compiled ink story".to_string()))?;
+
+        Converter::new(&story, self, commands, file).convert()
+    }
+}
+
+/// A problem found in a compiled ink story, `path` being where in the story it was found
+#[derive(Debug, Clone, PartialEq)]
+pub struct InkDiagnostic {
+    pub file: String,
+    pub path: String,
+    pub message: String,
+}
+impl InkDiagnostic {
+    fn new(file: &str, path: &str, message: impl ToString) -> Self {
+        Self {
+            file: file.to_string(),
+            path: path.to_string(),
+            message: message.to_string(),
+        }
+    }
+}
+impl Display for InkDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at {:?}: {}", self.file, self.path, self.message)
+    }
+}
+
+/// Every problem found in a compiled ink story
+#[derive(Debug, Clone, PartialEq)]
+pub struct InkError {
+    diagnostics: Vec<InkDiagnostic>,
+}
+impl InkError {
+    pub fn diagnostics(&self) -> &[InkDiagnostic] {
+        &self.diagnostics
+    }
+}
+impl Display for InkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.diagnostics
+            .iter()
+            .enumerate()
+            .try_for_each(|(index, diagnostic)| {
+                if index > 0 {
+                    writeln!(f)?;
+                }
+                write!(f, "{}", diagnostic)
+            })
+    }
+}
+impl std::error::Error for InkError {}
+
+/// Loads `.ink.json` files as a [`DialogueAsset`], using the external functions of the [`InkImporter`] resource and
+/// the commands of the [`DialogueCommands`] resource
+pub struct InkAssetLoader {
+    importer: InkImporter,
+    commands: DialogueCommands,
+}
+impl FromWorld for InkAssetLoader {
+    fn from_world(world: &mut World) -> Self {
+        Self {
+            importer: world
+                .get_resource::<InkImporter>()
+                .cloned()
+                .unwrap_or_default(),
+            commands: world
+                .get_resource::<DialogueCommands>()
+                .cloned()
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl AssetLoader for InkAssetLoader {
+    type Asset = DialogueAsset;
+    type Settings = ();
+    type Error = DialogueAssetError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a Self::Settings,
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
+        Box::pin(async move {
+            let mut source = String::new();
+            reader.read_to_string(&mut source).await?;
+
+            let paragraphs = self
+                .importer
+                .import_with(
+                    &source,
+                    &load_context.path().display().to_string(),
+                    &self.commands,
+                )
+                .map_err(DialogueAssetError::Ink)?;
+
+            Ok(DialogueAsset::new(paragraphs))
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ink.json"]
+    }
+}
+
+enum Item {
+    Container(usize),
+    Value(Value),
+}
+
+struct Container {
+    path: String,
+    parent: Option<usize>,
+    content: Vec<Item>,
+    named: HashMap<String, usize>,
+    /// Whether the container is part of its parent's content, rather than only being reachable by name
+    inline: bool,
+}
+
+/// The containers of a compiled story, the root being the first
+struct Story {
+    containers: Vec<Container>,
+}
+impl Story {
+    fn new(root: &Value) -> Option<Self> {
+        let mut story = Self {
+            containers: Vec::new(),
+        };
+        story.add(root, None, String::new(), true)?;
+        Some(story)
+    }
+
+    fn add(
+        &mut self,
+        value: &Value,
+        parent: Option<usize>,
+        path: String,
+        inline: bool,
+    ) -> Option<usize> {
+        let (terminator, content) = value.as_array()?.split_last()?;
+        let id = self.containers.len();
+        self.containers.push(Container {
+            path: path.clone(),
+            parent,
+            content: Vec::new(),
+            named: HashMap::new(),
+            inline,
+        });
+
+        for (index, item) in content.iter().enumerate() {
+            let item = if item.is_array() {
+                let name = item
+                    .as_array()
+                    .and_then(|item| item.last())
+                    .and_then(|terminator| terminator.get("#n"))
+                    .and_then(Value::as_str);
+                let child_path = join(&path, name.map_or(index.to_string(), str::to_string));
+                let child = self.add(item, Some(id), child_path, true)?;
+
+                if let Some(name) = name {
+                    self.containers[id].named.insert(name.to_string(), child);
+                }
+                Item::Container(child)
+            } else {
+                Item::Value(item.clone())
+            };
+            self.containers[id].content.push(item);
+        }
+
+        if let Some(terminator) = terminator.as_object() {
+            for (name, value) in terminator.iter().filter(|(_, value)| value.is_array()) {
+                let child = self.add(value, Some(id), join(&path, name), false)?;
+                self.containers[id].named.insert(name.clone(), child);
+            }
+        }
+
+        Some(id)
+    }
+
+    /// Finds the container and index a path points to, relative paths starting from the container of the object they're on
+    fn resolve(&self, from: usize, path: &str) -> Option<(usize, usize)> {
+        let (mut current, components) = match path.strip_prefix('.') {
+            Some(relative) => (from, relative.split('.').skip(1).collect::<Vec<_>>()),
+            None => (0, path.split('.').collect()),
+        };
+
+        for (position, component) in components.iter().enumerate() {
+            let container = &self.containers[current];
+
+            if *component == "^" {
+                current = container.parent?;
+            } else if let Ok(index) = component.parse::<usize>() {
+                match container.content.get(index) {
+                    Some(Item::Container(child)) => current = *child,
+                    _ if position + 1 == components.len() => return Some((current, index)),
+                    _ => return None,
+                }
+            } else {
+                current = *container.named.get(*component)?;
+            }
+        }
+
+        Some((current, 0))
+    }
+
+    fn label(&self, (container, index): (usize, usize)) -> String {
+        match index {
+            0 => self.containers[container].path.clone(),
+            index => join(&self.containers[container].path, index),
+        }
+    }
+
+    /// The text of a container that's only ever diverted to for its text, which ink does for the start of choices
+    fn inline_text(&self, container: usize) -> Option<String> {
+        let (last, texts) = self.containers[container].content.split_last()?;
+        let Item::Value(last) = last else {
+            return None;
+        };
+        if last.get("var").is_none() || last.get("->").and_then(Value::as_str) != Some("$r") {
+            return None;
+        }
+
+        texts
+            .iter()
+            .map(|item| match item {
+                Item::Value(Value::String(text)) => text.strip_prefix('^'),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+fn join(path: &str, name: impl Display) -> String {
+    if path.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", path, name)
+    }
+}
+
+enum Expression {
+    Value(DialogueCondition),
+    /// A variable with a number added to it, which can only be assigned back to the same variable
+    Increment(String, DialogueValue),
+    /// Something a diagnostic has already been given for
+    Unsupported,
+}
+
+struct Converter<'a> {
+    story: &'a Story,
+    importer: &'a InkImporter,
+    commands: &'a DialogueCommands,
+    file: &'a str,
+    diagnostics: Vec<InkDiagnostic>,
+    paragraphs: Vec<Paragraph>,
+    /// Places diverted to, which get an empty paragraph named after them
+    targets: HashSet<(usize, usize)>,
+    /// The variables recording that the content of a once-only choice has been seen
+    visits: HashMap<(usize, usize), String>,
+    line: String,
+    changes: Vec<VariableChange>,
+    action: Option<SentenceAction>,
+    options: Vec<ChoiceOption>,
+    fallback: Option<String>,
+    /// Whether the last paragraph is a plain line that choices and actions can be added to
+    attachable: bool,
+    glue: bool,
+    tag: bool,
+    stack: Vec<Expression>,
+    string: Option<String>,
+    globals: bool,
+}
+impl<'a> Converter<'a> {
+    fn new(
+        story: &'a Story,
+        importer: &'a InkImporter,
+        commands: &'a DialogueCommands,
+        file: &'a str,
+    ) -> Self {
+        Self {
+            story,
+            importer,
+            commands,
+            file,
+            diagnostics: Vec::new(),
+            paragraphs: Vec::new(),
+            targets: HashSet::new(),
+            visits: HashMap::new(),
+            line: String::new(),
+            changes: Vec::new(),
+            action: None,
+            options: Vec::new(),
+            fallback: None,
+            attachable: false,
+            glue: false,
+            tag: false,
+            stack: Vec::new(),
+            string: None,
+            globals: false,
+        }
+    }
+
+    fn error(&mut self, path: &str, message: impl ToString) {
+        self.diagnostics
+            .push(InkDiagnostic::new(self.file, path, message));
+    }
+
+    fn convert(mut self) -> Result<Vec<Paragraph>, InkError> {
+        let story = self.story;
+        self.collect_targets();
+
+        let mut queue = VecDeque::from([0]);
+        if let Some(globals) = story.containers[0].named.get("global decl") {
+            self.globals = true;
+            self.emit_container(*globals, &mut queue);
+            self.globals = false;
+        }
+
+        // Containers only reachable by diverting to them are laid out one after the other, each ending the dialogue
+        while let Some(container) = queue.pop_front() {
+            self.emit_container(container, &mut queue);
+            self.stop();
+        }
+
+        if self.diagnostics.is_empty() {
+            Ok(self.paragraphs)
+        } else {
+            Err(InkError {
+                diagnostics: self.diagnostics,
+            })
+        }
+    }
+
+    fn collect_targets(&mut self) {
+        let story = self.story;
+
+        story
+            .containers
+            .iter()
+            .enumerate()
+            .for_each(|(id, container)| {
+                container
+                    .content
+                    .iter()
+                    .enumerate()
+                    .for_each(|(index, item)| {
+                        let Item::Value(Value::Object(object)) = item else {
+                            return;
+                        };
+                        let Some(path) = object
+                            .get("->")
+                            .filter(|_| object.get("var").is_none())
+                            .or_else(|| object.get("*"))
+                            .and_then(Value::as_str)
+                        else {
+                            return;
+                        };
+
+                        match story.resolve(id, path) {
+                            Some(target) => {
+                                self.targets.insert(target);
+
+                                let flags = object.get("flg").and_then(Value::as_u64).unwrap_or(0);
+                                if object.contains_key("*") && flags & 0x10 != 0 {
+                                    self.visits
+                                        .insert(target, format!("{}#visited", story.label(target)));
+                                }
+                            }
+                            None => self.error(
+                                &story.label((id, index)),
+                                format!("divert to missing path {:?}", path),
+                            ),
+                        }
+                    });
+            });
+    }
+
+    fn emit_container(&mut self, id: usize, queue: &mut VecDeque<usize>) {
+        let story = self.story;
+        let container = &story.containers[id];
+
+        self.label((id, 0));
+        container
+            .content
+            .iter()
+            .enumerate()
+            .for_each(|(index, item)| {
+                if index > 0 {
+                    self.label((id, index));
+                }
+                match item {
+                    Item::Container(child) => self.emit_container(*child, queue),
+                    Item::Value(value) => self.instruction(id, index, value),
+                }
+            });
+        if !container.content.is_empty() {
+            self.label((id, container.content.len()));
+        }
+
+        queue.extend(container.named.iter().filter_map(|(name, child)| {
+            let nested = &story.containers[*child];
+            (!nested.inline && name != "global decl" && story.inline_text(*child).is_none())
+                .then_some(*child)
+        }));
+    }
+
+    fn label(&mut self, position: (usize, usize)) {
+        if !self.targets.contains(&position) {
+            return;
+        }
+        self.flush_line();
+
+        let mut paragraph = Paragraph::new().with_name(self.story.label(position));
+        if let Some(visited) = self.visits.get(&position) {
+            paragraph.add_variable_change(VariableChange::set(visited, true));
+        }
+        self.push(paragraph);
+    }
+
+    fn push(&mut self, paragraph: Paragraph) {
+        self.paragraphs.push(paragraph);
+        self.attachable = false;
+    }
+
+    fn stop(&mut self) {
+        self.flush_line();
+        self.flush_choices();
+        if !self.paragraphs.last().is_some_and(Paragraph::stops) {
+            self.push(Paragraph::new().with_stop(true));
+        }
+    }
+
+    fn flush_line(&mut self) {
+        let text = self.line.trim().to_string();
+        self.line.clear();
+        if text.is_empty() && self.changes.is_empty() {
+            return;
+        }
+
+        let mut paragraph = Paragraph::new();
+        if !text.is_empty() {
+            let mut sentence = Sentence::new().with_text(&text);
+            if let Some(action) = self.action.take() {
+                sentence.set_sentence_action(action);
+            }
+            paragraph.add_sentence(sentence);
+        }
+        self.changes
+            .drain(..)
+            .for_each(|change| paragraph.add_variable_change(change));
+
+        self.push(paragraph);
+        self.attachable = !text.is_empty();
+    }
+
+    fn flush_choices(&mut self) {
+        if self.options.is_empty() && self.fallback.is_none() {
+            return;
+        }
+        self.flush_line();
+
+        let choice = Choice::new().with_options(std::mem::take(&mut self.options));
+        let fallback = self.fallback.take();
+
+        match self.paragraphs.last_mut().filter(|_| self.attachable) {
+            Some(last) => {
+                last.set_choice(choice);
+                if let Some(fallback) = fallback {
+                    last.set_jump(fallback);
+                }
+                self.attachable = false;
+            }
+            None => {
+                let mut paragraph = Paragraph::new().with_choice(choice);
+                if let Some(fallback) = fallback {
+                    paragraph.set_jump(fallback);
+                }
+                self.push(paragraph);
+            }
+        }
+    }
+
+    fn pop(&mut self) -> Expression {
+        self.stack.pop().unwrap_or(Expression::Unsupported)
+    }
+
+    fn pop_text(&mut self, path: &str) -> String {
+        match self.pop() {
+            Expression::Value(DialogueCondition::Literal(value)) => value.to_string(),
+            Expression::Unsupported => String::new(),
+            _ => {
+                self.error(path, "choice text can only be plain text");
+                String::new()
+            }
+        }
+    }
+
+    fn instruction(&mut self, id: usize, index: usize, value: &Value) {
+        let path = self.story.label((id, index));
+
+        match value {
+            Value::String(command) => self.command(command, &path),
+            Value::Bool(value) => self.literal(*value),
+            Value::Number(number) => match number.as_i64() {
+                Some(number) => self.literal(number),
+                None => self.literal(number.as_f64().unwrap_or_default()),
+            },
+            Value::Object(object) => self.object(id, object, &path),
+            _ => self.error(&path, format!("unexpected {}", value)),
+        }
+    }
+
+    fn literal(&mut self, value: impl Into<DialogueValue>) {
+        self.stack
+            .push(Expression::Value(DialogueCondition::Literal(value.into())));
+    }
+
+    fn command(&mut self, command: &str, path: &str) {
+        if let Some(text) = command.strip_prefix('^') {
+            match self.string.as_mut() {
+                _ if self.tag => {}
+                Some(string) => string.push_str(text),
+                None => self.line.push_str(text),
+            }
+            return;
+        }
+
+        match command {
+            "\n" if self.glue => self.glue = false,
+            "\n" => self.flush_line(),
+            "<>" => self.glue = true,
+            "ev" | "/ev" | "nop" => {}
+            "str" => self.string = Some(String::new()),
+            "/str" => {
+                let string = self.string.take().unwrap_or_default();
+                self.literal(string);
+            }
+            "#" => self.tag = true,
+            "/#" => self.tag = false,
+            "done" | "end" if self.globals => {}
+            "done" | "end" => self.stop(),
+            "pop" => {
+                self.pop();
+            }
+            "void" => self.stack.push(Expression::Unsupported),
+            "out" => match self.pop() {
+                Expression::Value(DialogueCondition::Literal(value)) => {
+                    self.line.push_str(&value.to_string())
+                }
+                Expression::Unsupported => {}
+                _ => self.error(path, "printing variables isn't supported"),
+            },
+            "!" => match self.pop() {
+                Expression::Value(condition) => self.stack.push(Expression::Value(condition.not())),
+                _ => self.stack.push(Expression::Unsupported),
+            },
+            "_" => match self.pop() {
+                Expression::Value(DialogueCondition::Literal(DialogueValue::Int(value))) => {
+                    self.literal(-value)
+                }
+                Expression::Value(DialogueCondition::Literal(DialogueValue::Float(value))) => {
+                    self.literal(-value)
+                }
+                _ => {
+                    self.error(path, "only numbers can be negated");
+                    self.stack.push(Expression::Unsupported);
+                }
+            },
+            "==" | "!=" | "<" | "<=" | ">" | ">=" | "&&" | "||" | "+" | "-" => {
+                let right = self.pop();
+                let left = self.pop();
+                let result = self.binary(command, left, right, path);
+                self.stack.push(result);
+            }
+            _ => {
+                self.error(path, format!("unsupported ink command {:?}", command));
+                self.stack.push(Expression::Unsupported);
+            }
+        }
+    }
+
+    fn binary(
+        &mut self,
+        operator: &str,
+        left: Expression,
+        right: Expression,
+        path: &str,
+    ) -> Expression {
+        let (Expression::Value(left), Expression::Value(right)) = (left, right) else {
+            return Expression::Unsupported;
+        };
+
+        let comparison = match operator {
+            "&&" => return Expression::Value(left.and(right)),
+            "||" => return Expression::Value(left.or(right)),
+            "==" => Comparison::Equal,
+            "!=" => Comparison::NotEqual,
+            "<" => Comparison::Less,
+            "<=" => Comparison::LessEqual,
+            ">" => Comparison::Greater,
+            ">=" => Comparison::GreaterEqual,
+            _ => {
+                return match (left, operator, right) {
+                    (DialogueCondition::Variable(name), "+", DialogueCondition::Literal(value)) => {
+                        Expression::Increment(name, value)
+                    }
+                    (
+                        DialogueCondition::Variable(name),
+                        "-",
+                        DialogueCondition::Literal(DialogueValue::Int(value)),
+                    ) => Expression::Increment(name, DialogueValue::Int(-value)),
+                    (
+                        DialogueCondition::Variable(name),
+                        "-",
+                        DialogueCondition::Literal(DialogueValue::Float(value)),
+                    ) => Expression::Increment(name, DialogueValue::Float(-value)),
+                    _ => {
+                        self.error(path, "only adding a number to a variable is supported");
+                        Expression::Unsupported
+                    }
+                }
+            }
+        };
+
+        Expression::Value(DialogueCondition::Compare(
+            Box::new(left),
+            comparison,
+            Box::new(right),
+        ))
+    }
+
+    fn object(&mut self, id: usize, object: &Map<String, Value>, path: &str) {
+        let story = self.story;
+        let string = |key: &str| object.get(key).and_then(Value::as_str);
+
+        if let Some(target) = string("->") {
+            if object.contains_key("var") {
+                return self.error(path, "diverting to a variable isn't supported");
+            }
+            let Some(position) = story.resolve(id, target) else {
+                return;
+            };
+
+            if object.get("c").and_then(Value::as_bool) == Some(true) {
+                let condition = self.pop();
+                self.flush_line();
+
+                let mut paragraph = Paragraph::new().with_jump(story.label(position));
+                match condition {
+                    Expression::Value(condition) => paragraph.set_condition(condition),
+                    Expression::Increment(..) => {
+                        self.error(path, "a sum can't be used as a condition")
+                    }
+                    Expression::Unsupported => {}
+                }
+                self.push(paragraph);
+            } else if let Some(text) = (position.1 == 0)
+                .then(|| story.inline_text(position.0))
+                .flatten()
+            {
+                match self.string.as_mut() {
+                    Some(string) => string.push_str(&text),
+                    None => self.line.push_str(&text),
+                }
+            } else {
+                self.flush_line();
+                self.flush_choices();
+                self.push(Paragraph::new().with_jump(story.label(position)));
+            }
+        } else if let Some(target) = string("*") {
+            let flags = object.get("flg").and_then(Value::as_u64).unwrap_or(0);
+            let condition = (flags & 0x1 != 0).then(|| self.pop());
+            let choice_only = if flags & 0x4 != 0 {
+                self.pop_text(path)
+            } else {
+                String::new()
+            };
+            let start = if flags & 0x2 != 0 {
+                self.pop_text(path)
+            } else {
+                String::new()
+            };
+
+            let Some(position) = story.resolve(id, target) else {
+                return;
+            };
+            // An invisible default choice is taken when there are no others to pick
+            if flags & 0x8 != 0 {
+                self.fallback = Some(story.label(position));
+                return;
+            }
+
+            let mut condition = match condition {
+                Some(Expression::Value(condition)) => Some(condition),
+                Some(Expression::Increment(..)) => {
+                    self.error(path, "a sum can't be used as a condition");
+                    None
+                }
+                _ => None,
+            };
+            if let Some(visited) = self.visits.get(&position) {
+                let unvisited = DialogueCondition::variable(visited).not();
+                condition = Some(match condition {
+                    Some(condition) => condition.and(unvisited),
+                    None => unvisited,
+                });
+            }
+
+            let mut option = ChoiceOption::new(format!("{}{}", start, choice_only).trim())
+                .with_jump(story.label(position));
+            if let Some(condition) = condition {
+                option.set_condition(condition);
+            }
+            self.options.push(option);
+        } else if let Some(name) = string("VAR=") {
+            let change = match self.pop() {
+                Expression::Value(DialogueCondition::Literal(value)) => {
+                    Some(VariableChange::Set(name.to_string(), value))
+                }
+                Expression::Increment(variable, value) if variable == name => {
+                    Some(VariableChange::Add(name.to_string(), value))
+                }
+                Expression::Unsupported => None,
+                _ => {
+                    self.error(
+                        path,
+                        "only plain values, or adding to the same variable, can be assigned",
+                    );
+                    None
+                }
+            };
+            self.changes.extend(change);
+        } else if let Some(name) = string("VAR?") {
+            self.stack
+                .push(Expression::Value(DialogueCondition::variable(name)));
+        } else if let Some(name) = string("temp=") {
+            self.pop();
+            // Ink keeps where to return to after showing the start of a choice in `$r`
+            if name != "$r" {
+                self.error(path, "temporary variables aren't supported");
+            }
+        } else if object.contains_key("^->") {
+            self.stack.push(Expression::Unsupported);
+        } else if let Some(name) = string("x()") {
+            let count = object.get("exArgs").and_then(Value::as_u64).unwrap_or(0);
+            let mut arguments = (0..count).map(|_| self.pop()).collect::<Vec<_>>();
+            arguments.reverse();
+
+            if let Some(action) = self.external(name, arguments, path) {
+                self.attach_action(action, path);
+            }
+            self.stack.push(Expression::Unsupported);
+        } else if !object.contains_key("#") {
+            let message = match object.keys().next().map(String::as_str) {
+                Some("CNT?") => "read counts aren't supported",
+                Some("f()") => "functions aren't supported",
+                Some("->t->") => "tunnels aren't supported",
+                Some("list") => "lists aren't supported",
+                _ => "unsupported ink object",
+            };
+            self.error(path, message);
+            self.stack.push(Expression::Unsupported);
+        }
+    }
+
+    /// The action run for a call to an external function
+    fn external(
+        &mut self,
+        name: &str,
+        arguments: Vec<Expression>,
+        path: &str,
+    ) -> Option<SentenceAction> {
+        if let Some(action) = self.importer.get_external(name) {
+            if !arguments.is_empty() {
+                self.error(
+                    path,
+                    format!(
+                        "{:?} can't take arguments, add it to DialogueCommands instead",
+                        name
+                    ),
+                );
+            }
+            return Some(action);
+        }
+        if !self.commands.contains(name) {
+            self.error(
+                path,
+                format!("no external function or command named {:?} was added", name),
+            );
+            return None;
+        }
+
+        let mut values = Vec::new();
+        let mut variables = Vec::new();
+        for (index, argument) in arguments.into_iter().enumerate() {
+            match argument {
+                Expression::Value(DialogueCondition::Literal(value)) => values.push(value),
+                Expression::Value(DialogueCondition::Variable(variable)) => {
+                    // Checked once the variable's value is known
+                    values.push(DialogueValue::Int(0));
+                    variables.push((index, variable));
+                }
+                Expression::Unsupported => return None,
+                _ => {
+                    self.error(
+                        path,
+                        "arguments to external functions can only be values or variables",
+                    );
+                    return None;
+                }
+            }
+        }
+
+        if variables.is_empty() {
+            return match self.commands.check(name, values) {
+                Ok(command) => Some(SentenceAction::Command(command)),
+                Err(error) => {
+                    self.error(path, error);
+                    None
+                }
+            };
+        }
+        if let Err(error @ CommandError::ArgumentCount { .. }) =
+            self.commands.check(name, values.clone())
+        {
+            self.error(path, error);
+            return None;
+        }
+
+        let name = name.to_string();
+        Some(SentenceAction::closure(move |dialogue, world| {
+            let mut arguments = values.clone();
+            for (index, variable) in &variables {
+                let Some(value) = world.resource::<DialogueVariables>().get(variable).cloned()
+                else {
+                    warn!("Could not call <<{name}>> in {dialogue:?}, {variable:?} isn't set");
+                    return;
+                };
+                arguments[*index] = value;
+            }
+            let commands = world.resource::<DialogueCommands>().clone();
+            match commands.check(&name, arguments) {
+                Ok(command) => SentenceAction::Command(command).run(dialogue, world),
+                Err(error) => warn!("Could not call <<{name}>> in {dialogue:?}: {error}"),
+            }
+        }))
+    }
+
+    /// Runs the action once the player moves past the line shown before the call
+    fn attach_action(&mut self, action: SentenceAction, path: &str) {
+        if !self.line.trim().is_empty() && self.action.is_none() {
+            self.action = Some(action);
+            return;
+        }
+
+        match self
+            .paragraphs
+            .last_mut()
+            .filter(|_| self.attachable && self.line.trim().is_empty())
+            .and_then(|last| last.mut_sentences().last_mut())
+            .filter(|sentence| sentence.get_action().is_none())
+        {
+            Some(sentence) => sentence.set_sentence_action(action),
+            None => self.error(
+                path,
+                "external functions can only be called once after each line of text",
+            ),
+        }
+    }
+}
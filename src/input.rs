@@ -0,0 +1,292 @@
+use crate::prelude::*;
+use bevy::{ecs::system::SystemParam, input::touch::Touches};
+use serde::{Deserialize, Serialize};
+
+/// Something the player can ask a dialogue to do, whatever input it's bound to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DialogueAction {
+    /// Finishes the typewriter, or moves on to the next sentence or paragraph
+    Advance,
+    /// Starts or stops skipping ahead to the next choice or the end of the dialogue, see [`Dialogue::start_skipping`]
+    SkipAll,
+    /// Races through the dialogue for as long as it's held, see [`Dialogue::set_fast_forward`]
+    FastForward,
+    /// Turns auto mode on or off, see [`Dialogue::set_auto`]
+    ToggleAuto,
+    ChoiceUp,
+    ChoiceDown,
+    ChoiceConfirm,
+    /// Isn't handled by the dialogue itself, read [`DialogueInput`] events to show your own backlog
+    OpenBacklog,
+}
+impl DialogueAction {
+    /// Held actions are triggered every frame their input is held down rather than once when it is pressed
+    pub fn is_held(&self) -> bool {
+        matches!(self, Self::FastForward)
+    }
+}
+
+/// A physical input that can trigger a [`DialogueAction`]
+/// ```
+/// # use bevy_dialogue_system::prelude::*;
+/// # use bevy::{input::{mouse::MouseButtonInput, ButtonState}, prelude::*};
+/// let mut app = App::new();
+/// app.add_plugins((MinimalPlugins, AssetPlugin::default(), bevy::input::InputPlugin, DialoguePlugin));
+/// # app.finish();
+/// # app.cleanup();
+///
+/// let dialogue = app
+///     .world
+///     .spawn(Dialogue::new().with_paragraphs(vec![
+///         Paragraph::new().with_sentences(vec![Sentence::new().with_text("Hello")]),
+///         Paragraph::new().with_sentences(vec![Sentence::new().with_text("there")]),
+///     ]))
+///     .id();
+/// let click = |app: &mut App| {
+///     let window = Entity::PLACEHOLDER;
+///     app.world.send_event(MouseButtonInput { button: MouseButton::Left, state: ButtonState::Pressed, window });
+///     app.world.send_event(MouseButtonInput { button: MouseButton::Left, state: ButtonState::Released, window });
+///     app.update();
+/// };
+/// let current = |app: &App| {
+///     let paragraph = app.world.get::<Dialogue>(dialogue).unwrap().get_current_paragraph().unwrap();
+///     paragraph.sentences()[0].text().to_string()
+/// };
+/// app.update();
+///
+/// // Clicking a button doesn't advance the dialogue behind it
+/// let button = app.world.spawn(Interaction::Pressed).id();
+/// click(&mut app);
+/// assert_eq!(current(&app), "Hello");
+///
+/// app.world.despawn(button);
+/// click(&mut app);
+/// assert_eq!(current(&app), "there");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum InputBinding {
+    Key(KeyCode),
+    /// The button on any connected gamepad
+    GamepadButton(GamepadButtonType),
+    /// Clicking anywhere but on UI that reacts to the pointer, such as the options of a choice
+    MouseButton(MouseButton),
+    /// Tapping the screen anywhere but on UI that reacts to the pointer
+    Touch,
+}
+impl From<KeyCode> for InputBinding {
+    fn from(key: KeyCode) -> Self {
+        Self::Key(key)
+    }
+}
+impl From<GamepadButtonType> for InputBinding {
+    fn from(button: GamepadButtonType) -> Self {
+        Self::GamepadButton(button)
+    }
+}
+impl From<MouseButton> for InputBinding {
+    fn from(button: MouseButton) -> Self {
+        Self::MouseButton(button)
+    }
+}
+
+/// Which inputs trigger which [`DialogueAction`]s for a [`Dialogue`], so that they can be rebound
+///
+/// Use [`DialogueBindings::empty`] to leave every action to [`DialogueInput`] events sent by your own input handling
+/// ```
+/// # use bevy_dialogue_system::prelude::*;
+/// # use bevy::prelude::*;
+/// let bindings = DialogueBindings::default()
+///     .with(DialogueAction::FastForward, KeyCode::ShiftLeft)
+///     .without(DialogueAction::Advance, MouseButton::Left);
+///
+/// assert!(bindings.get(DialogueAction::FastForward).contains(&InputBinding::Key(KeyCode::ShiftLeft)));
+/// assert!(!bindings.get(DialogueAction::Advance).contains(&InputBinding::MouseButton(MouseButton::Left)));
+/// assert_eq!(bindings.get(DialogueAction::ToggleAuto), &[InputBinding::GamepadButton(GamepadButtonType::North)]);
+/// assert!(DialogueBindings::empty().get(DialogueAction::Advance).is_empty());
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DialogueBindings(HashMap<DialogueAction, Vec<InputBinding>>);
+
+impl Default for DialogueBindings {
+    fn default() -> Self {
+        Self::empty()
+            .with(DialogueAction::Advance, KeyCode::Space)
+            .with(DialogueAction::Advance, KeyCode::Enter)
+            .with(DialogueAction::Advance, GamepadButtonType::South)
+            .with(DialogueAction::Advance, MouseButton::Left)
+            .with(DialogueAction::Advance, InputBinding::Touch)
+            .with(DialogueAction::SkipAll, KeyCode::Tab)
+            .with(DialogueAction::FastForward, KeyCode::ControlLeft)
+            .with(DialogueAction::FastForward, KeyCode::ControlRight)
+            .with(DialogueAction::FastForward, GamepadButtonType::RightTrigger)
+            // No key, as letters are usually taken by the game itself
+            .with(DialogueAction::ToggleAuto, GamepadButtonType::North)
+            .with(DialogueAction::ChoiceUp, KeyCode::ArrowUp)
+            .with(DialogueAction::ChoiceUp, GamepadButtonType::DPadUp)
+            .with(DialogueAction::ChoiceDown, KeyCode::ArrowDown)
+            .with(DialogueAction::ChoiceDown, GamepadButtonType::DPadDown)
+            // Clicks and taps confirm through the option's button instead
+            .with(DialogueAction::ChoiceConfirm, KeyCode::Space)
+            .with(DialogueAction::ChoiceConfirm, KeyCode::Enter)
+            .with(DialogueAction::ChoiceConfirm, GamepadButtonType::South)
+    }
+}
+impl DialogueBindings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Nothing bound at all
+    pub fn empty() -> Self {
+        Self(HashMap::new())
+    }
+    pub fn with(mut self, action: DialogueAction, binding: impl Into<InputBinding>) -> Self {
+        self.bind(action, binding);
+        self
+    }
+    pub fn without(mut self, action: DialogueAction, binding: impl Into<InputBinding>) -> Self {
+        self.unbind(action, binding);
+        self
+    }
+    pub fn bind(&mut self, action: DialogueAction, binding: impl Into<InputBinding>) {
+        let binding = binding.into();
+        let bindings = self.0.entry(action).or_default();
+
+        if !bindings.contains(&binding) {
+            bindings.push(binding);
+        }
+    }
+    pub fn unbind(&mut self, action: DialogueAction, binding: impl Into<InputBinding>) {
+        let binding = binding.into();
+
+        if let Some(bindings) = self.0.get_mut(&action) {
+            bindings.retain(|other| *other != binding);
+        }
+    }
+    /// Unbinds the input from every action
+    pub fn unbind_all(&mut self, binding: impl Into<InputBinding>) {
+        let binding = binding.into();
+
+        self.0
+            .values_mut()
+            .for_each(|bindings| bindings.retain(|other| *other != binding));
+    }
+    /// Replaces everything bound to the action
+    pub fn set(&mut self, action: DialogueAction, bindings: Vec<InputBinding>) {
+        self.0.insert(action, bindings);
+    }
+    pub fn remove(&mut self, action: DialogueAction) {
+        self.0.remove(&action);
+    }
+    pub fn get(&self, action: DialogueAction) -> &[InputBinding] {
+        self.0.get(&action).map_or(&[], Vec::as_slice)
+    }
+    pub fn get_mut(&mut self, action: DialogueAction) -> &mut Vec<InputBinding> {
+        self.0.entry(action).or_default()
+    }
+    pub fn iter(&self) -> impl Iterator<Item = (&DialogueAction, &Vec<InputBinding>)> {
+        self.0.iter()
+    }
+}
+
+/// Asks dialogues to perform an action, sent by the plugin for bound inputs or by your own systems
+/// ```
+/// # use bevy_dialogue_system::prelude::*;
+/// # use bevy::prelude::*;
+/// let mut app = App::new();
+/// app.add_plugins((MinimalPlugins, AssetPlugin::default(), bevy::input::InputPlugin, DialoguePlugin));
+/// # app.finish();
+/// # app.cleanup();
+///
+/// let dialogue = app
+///     .world
+///     .spawn(Dialogue::new().with_bindings(DialogueBindings::empty()).with_paragraphs(vec![
+///         Paragraph::new().with_sentences(vec![Sentence::new().with_text("Hello")]),
+///         Paragraph::new().with_sentences(vec![Sentence::new().with_text("there")]),
+///     ]))
+///     .id();
+/// app.update();
+///
+/// app.world.send_event(DialogueInput::to(dialogue, DialogueAction::Advance));
+/// app.update();
+///
+/// let dialogue = app.world.get::<Dialogue>(dialogue).unwrap();
+/// assert_eq!(dialogue.get_current_paragraph().unwrap().sentences()[0].text(), "there");
+/// ```
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct DialogueInput {
+    pub action: DialogueAction,
+    /// The dialogue to act on, or every dialogue if `None`
+    pub dialogue: Option<Entity>,
+}
+impl DialogueInput {
+    /// Acts on every dialogue
+    pub fn all(action: DialogueAction) -> Self {
+        Self {
+            action,
+            dialogue: None,
+        }
+    }
+    pub fn to(dialogue: Entity, action: DialogueAction) -> Self {
+        Self {
+            action,
+            dialogue: Some(dialogue),
+        }
+    }
+    /// Whether the input is for `dialogue` and asks for `action`
+    pub fn is(&self, dialogue: Entity, action: DialogueAction) -> bool {
+        self.action == action && self.dialogue.is_none_or(|target| target == dialogue)
+    }
+}
+
+/// Every kind of input a binding can be read from
+#[derive(SystemParam)]
+pub(crate) struct BindingInput<'w, 's> {
+    keys: Res<'w, ButtonInput<KeyCode>>,
+    gamepad_buttons: Res<'w, ButtonInput<GamepadButton>>,
+    mouse_buttons: Res<'w, ButtonInput<MouseButton>>,
+    touches: Res<'w, Touches>,
+    interactions: Query<'w, 's, &'static Interaction>,
+}
+impl BindingInput<'_, '_> {
+    /// Whether one of the action's bindings was just pressed, or is being held for held actions
+    pub(crate) fn triggers(&self, bindings: &DialogueBindings, action: DialogueAction) -> bool {
+        // A click on a button is meant for the button, not for the dialogue behind it
+        let on_ui = self
+            .interactions
+            .iter()
+            .any(|interaction| *interaction != Interaction::None);
+
+        bindings.get(action).iter().any(|binding| {
+            let pointer = matches!(binding, InputBinding::MouseButton(_) | InputBinding::Touch);
+            if pointer && on_ui {
+                false
+            } else if action.is_held() {
+                self.pressed(binding)
+            } else {
+                self.just_pressed(binding)
+            }
+        })
+    }
+    fn just_pressed(&self, binding: &InputBinding) -> bool {
+        match binding {
+            InputBinding::Key(key) => self.keys.just_pressed(*key),
+            InputBinding::GamepadButton(button_type) => self
+                .gamepad_buttons
+                .get_just_pressed()
+                .any(|button| button.button_type == *button_type),
+            InputBinding::MouseButton(button) => self.mouse_buttons.just_pressed(*button),
+            InputBinding::Touch => self.touches.any_just_pressed(),
+        }
+    }
+    fn pressed(&self, binding: &InputBinding) -> bool {
+        match binding {
+            InputBinding::Key(key) => self.keys.pressed(*key),
+            InputBinding::GamepadButton(button_type) => self
+                .gamepad_buttons
+                .get_pressed()
+                .any(|button| button.button_type == *button_type),
+            InputBinding::MouseButton(button) => self.mouse_buttons.pressed(*button),
+            InputBinding::Touch => self.touches.iter().next().is_some(),
+        }
+    }
+}
@@ -1,5 +1,20 @@
+pub(crate) mod action;
+pub(crate) mod asset;
+pub(crate) mod choice;
+pub(crate) mod command;
+pub(crate) mod condition;
 pub(crate) mod dialogue;
+pub(crate) mod effect;
+pub(crate) mod event;
+pub(crate) mod ink;
+pub(crate) mod input;
+pub(crate) mod markup;
 pub(crate) mod paragraph;
 pub(crate) mod plugin;
 pub mod prelude;
+pub(crate) mod reveal;
 pub(crate) mod sentence;
+pub(crate) mod variables;
+pub(crate) mod voice;
+pub(crate) mod wait;
+pub(crate) mod yarn;
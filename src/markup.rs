@@ -0,0 +1,188 @@
+use crate::prelude::*;
+
+/// A run of a sentence's text styled by markup, `start` and `end` being byte offsets into the text
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MarkupSpan {
+    pub start: usize,
+    pub end: usize,
+    pub color: Option<Color>,
+    pub font_size: Option<f32>,
+    pub bold: bool,
+    pub effects: Vec<TextEffect>,
+}
+impl MarkupSpan {
+    fn same_style(&self, other: &Self) -> bool {
+        self.color == other.color
+            && self.font_size == other.font_size
+            && self.bold == other.bold
+            && self.effects == other.effects
+    }
+}
+
+enum Tag {
+    Color(Color),
+    Size(f32),
+    Bold,
+    Effect(TextEffect),
+}
+impl Tag {
+    fn parse(tag: &str) -> Option<Self> {
+        match tag.split_once('=') {
+            Some(("color", color)) => parse_color(color).map(Self::Color),
+            Some(("size", size)) => size.parse().ok().map(Self::Size),
+            None if tag == "b" => Some(Self::Bold),
+            None => TextEffect::parse(tag).map(Self::Effect),
+            _ => None,
+        }
+    }
+    fn name(&self) -> &str {
+        match self {
+            Self::Color(_) => "color",
+            Self::Size(_) => "size",
+            Self::Bold => "b",
+            Self::Effect(effect) => effect.name(),
+        }
+    }
+}
+
+fn parse_color(color: &str) -> Option<Color> {
+    Some(match color {
+        "white" => Color::WHITE,
+        "black" => Color::BLACK,
+        "red" => Color::RED,
+        "green" => Color::GREEN,
+        "blue" => Color::BLUE,
+        "yellow" => Color::YELLOW,
+        "cyan" => Color::CYAN,
+        "purple" => Color::PURPLE,
+        "orange" => Color::ORANGE,
+        "pink" => Color::PINK,
+        "gray" | "grey" => Color::GRAY,
+        hex => return Color::hex(hex).ok(),
+    })
+}
+
+/// Something that changes how the typewriter reveals the text from a point in it onwards
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TypeWriterCue {
+    /// Waits this many seconds before revealing any more
+    Wait(f32),
+    /// Reveals the rest this many times as fast as the typewriter's speed, `{speed=1}` going back to normal
+    Speed(f32),
+}
+impl TypeWriterCue {
+    fn parse(cue: &str) -> Option<Self> {
+        match cue.split_once('=') {
+            Some(("wait", seconds)) => seconds.trim().parse().ok().map(Self::Wait),
+            Some(("speed", speed)) => speed
+                .trim()
+                .parse()
+                .ok()
+                .filter(|speed: &f32| *speed > 0.)
+                .map(Self::Speed),
+            _ => None,
+        }
+    }
+}
+
+pub(crate) struct Markup {
+    pub text: String,
+    pub spans: Vec<MarkupSpan>,
+    /// Cues along with the byte offset into `text` they come before
+    pub cues: Vec<(usize, TypeWriterCue)>,
+}
+
+/// Splits markup into its text, the spans styled by `[color=red]`, `[size=64]`, `[b]` and [`TextEffect`] tags,
+/// and typewriter cues such as `{wait=0.5}` and `{speed=2}`
+///
+/// Tags that aren't recognised are kept as text, `[[` is a literal `[` and `{{` a literal `{`
+pub(crate) fn parse_markup(markup: &str) -> Markup {
+    let mut text = String::new();
+    let mut spans: Vec<MarkupSpan> = Vec::new();
+    let mut cues = Vec::new();
+    let mut open: Vec<Tag> = Vec::new();
+    let mut rest = markup;
+
+    while let Some(start) = rest.find(['[', '{']) {
+        push_text(&mut text, &mut spans, &open, &rest[..start]);
+        rest = &rest[start..];
+
+        let (opening, closing) = if rest.starts_with('[') {
+            ('[', ']')
+        } else {
+            ('{', '}')
+        };
+        if rest[1..].starts_with(opening) {
+            push_text(&mut text, &mut spans, &open, &rest[..1]);
+            rest = &rest[2..];
+            continue;
+        }
+
+        let Some(end) = rest.find(closing) else {
+            break;
+        };
+        let tag = rest[1..end].trim();
+
+        let handled = if opening == '{' {
+            TypeWriterCue::parse(tag)
+                .map(|cue| cues.push((text.len(), cue)))
+                .is_some()
+        } else {
+            match tag.strip_prefix('/') {
+                Some(name) => match open.iter().rposition(|tag| tag.name() == name) {
+                    Some(index) => {
+                        open.remove(index);
+                        true
+                    }
+                    None => false,
+                },
+                None => match Tag::parse(tag) {
+                    Some(tag) => {
+                        open.push(tag);
+                        true
+                    }
+                    None => false,
+                },
+            }
+        };
+
+        if !handled {
+            push_text(&mut text, &mut spans, &open, &rest[..=end]);
+        }
+        rest = &rest[end + 1..];
+    }
+    push_text(&mut text, &mut spans, &open, rest);
+
+    Markup { text, spans, cues }
+}
+
+fn push_text(text: &mut String, spans: &mut Vec<MarkupSpan>, open: &[Tag], value: &str) {
+    if value.is_empty() {
+        return;
+    }
+
+    let mut span = MarkupSpan {
+        start: text.len(),
+        end: text.len() + value.len(),
+        ..Default::default()
+    };
+    open.iter().for_each(|tag| match tag {
+        Tag::Color(color) => span.color = Some(*color),
+        Tag::Size(size) => span.font_size = Some(*size),
+        Tag::Bold => span.bold = true,
+        Tag::Effect(effect) => {
+            if !span.effects.contains(effect) {
+                span.effects.push(*effect);
+            }
+        }
+    });
+    text.push_str(value);
+
+    if open.is_empty() {
+        return;
+    }
+    match spans.last_mut() {
+        Some(last) if last.end == span.start && last.same_style(&span) => last.end = span.end,
+        _ => spans.push(span),
+    }
+}
@@ -1,17 +1,76 @@
 use crate::prelude::*;
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone)]
 pub struct Paragraph {
+    name: Option<String>,
+    jump: Option<String>,
+    stop: bool,
     sentences: Vec<Sentence>,
     current_sentence: usize,
     position: Option<UiRect>,
     width: Option<Val>,
+    choice: Option<Choice>,
+    condition: Option<DialogueCondition>,
+    variable_changes: Vec<VariableChange>,
+    commands: Vec<DialogueCommand>,
+    started: bool,
+    skipped: bool,
 }
 impl Paragraph {
     pub fn new() -> Self {
         Self::default()
     }
 
+    /// The name other paragraphs, sentences and options can use to jump to this paragraph
+    pub fn with_name(mut self, name: impl ToString) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+    pub fn without_name(mut self) -> Self {
+        self.name = None;
+        self
+    }
+    pub fn set_name(&mut self, name: impl ToString) {
+        self.name = Some(name.to_string());
+    }
+    pub fn reset_name(&mut self) {
+        self.name = None;
+    }
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// The name of the paragraph to go to after this one instead of the next
+    pub fn with_jump(mut self, target: impl ToString) -> Self {
+        self.jump = Some(target.to_string());
+        self
+    }
+    pub fn without_jump(mut self) -> Self {
+        self.jump = None;
+        self
+    }
+    pub fn set_jump(&mut self, target: impl ToString) {
+        self.jump = Some(target.to_string());
+    }
+    pub fn reset_jump(&mut self) {
+        self.jump = None;
+    }
+    pub fn get_jump(&self) -> Option<&String> {
+        self.jump.as_ref()
+    }
+
+    /// Whether the dialogue ends after this paragraph instead of carrying on to the next
+    pub fn with_stop(mut self, stop: bool) -> Self {
+        self.stop = stop;
+        self
+    }
+    pub fn set_stop(&mut self, stop: bool) {
+        self.stop = stop;
+    }
+    pub fn stops(&self) -> bool {
+        self.stop
+    }
+
     /// Sets the sentences in the Paragraph to be equal to `sentences`
     /// ```
     /// # use bevy_dialogue_system::prelude::*;
@@ -52,6 +111,9 @@ impl Paragraph {
     pub fn get_current_sentence_mut(&mut self) -> Option<&mut Sentence> {
         self.sentences.get_mut(self.current_sentence)
     }
+    pub fn current_sentence_index(&self) -> usize {
+        self.current_sentence
+    }
 
     pub fn with_position(mut self, position: UiRect) -> Self {
         self.position = Some(position);
@@ -95,28 +157,334 @@ impl Paragraph {
         self.width.as_mut()
     }
 
+    pub fn with_choice(mut self, choice: Choice) -> Self {
+        self.choice = Some(choice);
+        self
+    }
+    pub fn without_choice(mut self) -> Self {
+        self.choice = None;
+        self
+    }
+    pub fn set_choice(&mut self, choice: Choice) {
+        self.choice = Some(choice);
+    }
+    pub fn reset_choice(&mut self) {
+        self.choice = None;
+    }
+    pub fn get_choice(&self) -> Option<&Choice> {
+        self.choice.as_ref()
+    }
+    pub fn get_choice_mut(&mut self) -> Option<&mut Choice> {
+        self.choice.as_mut()
+    }
+
+    /// Only show the paragraph if `condition` holds when the dialogue reaches it
+    pub fn with_condition(mut self, condition: DialogueCondition) -> Self {
+        self.condition = Some(condition);
+        self
+    }
+    pub fn without_condition(mut self) -> Self {
+        self.condition = None;
+        self
+    }
+    pub fn set_condition(&mut self, condition: DialogueCondition) {
+        self.condition = Some(condition);
+    }
+    pub fn reset_condition(&mut self) {
+        self.condition = None;
+    }
+    pub fn get_condition(&self) -> Option<&DialogueCondition> {
+        self.condition.as_ref()
+    }
+
+    /// Changes made to the [`DialogueVariables`] as soon as the paragraph is shown
+    pub fn with_variable_changes(mut self, changes: Vec<VariableChange>) -> Self {
+        self.variable_changes = changes;
+        self
+    }
+    pub fn push_variable_change(mut self, change: VariableChange) -> Self {
+        self.variable_changes.push(change);
+        self
+    }
+    pub fn set_variable_changes(&mut self, changes: Vec<VariableChange>) {
+        self.variable_changes = changes;
+    }
+    pub fn add_variable_change(&mut self, change: VariableChange) {
+        self.variable_changes.push(change);
+    }
+    pub fn variable_changes(&self) -> &Vec<VariableChange> {
+        &self.variable_changes
+    }
+
+    /// Commands of [`DialogueCommands`] run as soon as the paragraph is shown
+    pub fn with_commands(mut self, commands: Vec<DialogueCommand>) -> Self {
+        self.commands = commands;
+        self
+    }
+    pub fn push_command(mut self, command: DialogueCommand) -> Self {
+        self.commands.push(command);
+        self
+    }
+    pub fn set_commands(&mut self, commands: Vec<DialogueCommand>) {
+        self.commands = commands;
+    }
+    pub fn add_command(&mut self, command: DialogueCommand) {
+        self.commands.push(command);
+    }
+    pub fn commands(&self) -> &Vec<DialogueCommand> {
+        &self.commands
+    }
+
     pub fn advance_sentence(&mut self) {
         self.current_sentence += 1;
     }
 
-    pub fn update_typewriter(&mut self, amount: f32) {
+    pub fn is_started(&self) -> bool {
+        self.started
+    }
+    /// Whether the paragraph was passed over because its condition didn't hold
+    pub fn is_skipped(&self) -> bool {
+        self.skipped
+    }
+    /// Checks the condition of the paragraph and moves to the first sentence that should be shown
+    ///
+    /// Returns whether there is anything to show, either a sentence or a choice
+    /// ```
+    /// # use bevy_dialogue_system::prelude::*;
+    /// let mut variables = DialogueVariables::new().with("has_key", false);
+    /// let mut paragraph = Paragraph::new().with_sentences(vec![
+    ///     Sentence::new().with_text("The door is unlocked").with_condition(DialogueCondition::variable("has_key")),
+    ///     Sentence::new().with_text("The door is locked").with_condition(DialogueCondition::variable("has_key").not()),
+    /// ]);
+    ///
+    /// assert!(paragraph.start(&mut variables));
+    /// assert_eq!(paragraph.get_current_sentence().unwrap().text(), "The door is locked");
+    /// assert!(paragraph.sentences()[0].is_skipped());
+    /// ```
+    pub fn start(&mut self, variables: &mut DialogueVariables) -> bool {
+        self.started = true;
+
+        if self
+            .condition
+            .as_ref()
+            .is_some_and(|condition| !condition.evaluate(variables))
+        {
+            self.skipped = true;
+            return false;
+        }
+        self.variable_changes
+            .iter()
+            .for_each(|change| variables.apply(change));
+
+        self.current_sentence = 0;
+        if let Some(choice) = self.choice.as_mut() {
+            choice.update_options(variables);
+        }
+        match self.get_current_sentence_mut() {
+            Some(sentence) if !sentence.condition_holds(variables) => {
+                sentence.skip();
+                self.advance_visible_sentence(variables);
+            }
+            Some(sentence) => sentence.apply_variable_changes(variables),
+            None => {}
+        }
+
+        self.get_current_sentence()
+            .is_some_and(|sentence| !sentence.is_skipped())
+            || self
+                .choice
+                .as_ref()
+                .is_some_and(|choice| choice.has_visible_options())
+    }
+    /// The index of the next sentence whose condition holds
+    pub fn next_visible_sentence(&self, variables: &DialogueVariables) -> Option<usize> {
+        self.sentences
+            .iter()
+            .enumerate()
+            .skip(self.current_sentence + 1)
+            .find(|(_, sentence)| sentence.condition_holds(variables))
+            .map(|(index, _)| index)
+    }
+    /// Moves on to the next sentence whose condition holds, skipping over any in between
+    ///
+    /// When there are none left every remaining sentence is skipped
+    /// ```
+    /// # use bevy_dialogue_system::prelude::*;
+    /// let mut variables = DialogueVariables::new().with("gold", 3);
+    /// let mut paragraph = Paragraph::new().with_sentences(vec![
+    ///     Sentence::new().with_text("Let's see... "),
+    ///     Sentence::new().with_text("You're rich! ").with_condition(DialogueCondition::parse("gold >= 10").unwrap()),
+    ///     Sentence::new().with_text("You're broke.").with_condition(DialogueCondition::parse("gold < 10").unwrap()),
+    /// ]);
+    ///
+    /// paragraph.advance_visible_sentence(&mut variables);
+    ///
+    /// assert_eq!(paragraph.get_current_sentence().unwrap().text(), "You're broke.");
+    /// assert!(paragraph.sentences()[1].is_skipped());
+    /// assert!(paragraph.all_paragraphs_visible());
+    /// ```
+    pub fn advance_visible_sentence(&mut self, variables: &mut DialogueVariables) {
+        let next = self.next_visible_sentence(variables);
+
+        self.sentences
+            .iter_mut()
+            .take(next.unwrap_or(usize::MAX))
+            .skip(self.current_sentence + 1)
+            .for_each(|sentence| sentence.skip());
+
+        match next {
+            Some(next) => {
+                self.current_sentence = next;
+                self.sentences[next].apply_variable_changes(variables);
+            }
+            None => self.current_sentence = self.sentences.len().saturating_sub(1),
+        }
+        if let Some(choice) = self.choice.as_mut() {
+            choice.update_options(variables);
+        }
+    }
+    /// Skips every remaining sentence once the current one has been displayed if none of them would be shown
+    pub fn skip_hidden_sentences(&mut self, variables: &DialogueVariables) {
+        if self.all_characters_displayed() && self.next_visible_sentence(variables).is_none() {
+            self.sentences
+                .iter_mut()
+                .skip(self.current_sentence + 1)
+                .for_each(|sentence| sentence.skip());
+        }
+    }
+
+    /// Hides every sentence again so the paragraph can be shown from the start
+    /// ```
+    /// # use bevy_dialogue_system::prelude::*;
+    /// let mut paragraph = Paragraph::new().with_sentences(vec![
+    ///     Sentence::new().with_text("One"),
+    ///     Sentence::new().with_text("Two").create_typewriter(),
+    /// ]);
+    ///
+    /// paragraph.advance_sentence();
+    /// paragraph.update_typewriter(1., &TypeWriterSettings::default());
+    /// paragraph.reset();
+    ///
+    /// assert_eq!(paragraph.get_current_sentence().unwrap().text(), "One");
+    /// assert_eq!(paragraph.sentences()[1].typewriter().current_time(), 0.);
+    /// ```
+    pub fn reset(&mut self) {
+        self.current_sentence = 0;
+        self.started = false;
+        self.skipped = false;
+        self.sentences.iter_mut().for_each(|sentence| {
+            sentence.mut_typewriter().reset();
+            sentence.unskip();
+        });
+        if let Some(choice) = self.choice.as_mut() {
+            choice.reset();
+        }
+    }
+
+    /// Whether `other` has the same number of sentences, so progress through one can be carried over to the other
+    pub fn matches_layout(&self, other: &Paragraph) -> bool {
+        self.sentences.len() == other.sentences.len()
+            && self.choice.as_ref().map(Choice::len) == other.choice.as_ref().map(Choice::len)
+    }
+    /// Carries over how far through `other` the dialogue has got, keeping the text and settings of this paragraph
+    pub fn copy_progress(&mut self, other: &Paragraph) {
+        self.current_sentence = other.current_sentence;
+        self.started = other.started;
+        self.skipped = other.skipped;
+        self.sentences
+            .iter_mut()
+            .zip(other.sentences.iter())
+            .for_each(|(sentence, other)| {
+                sentence
+                    .mut_typewriter()
+                    .set_time(other.typewriter().current_time());
+                if other.is_skipped() {
+                    sentence.skip();
+                } else {
+                    sentence.unskip();
+                }
+            });
+        if let (Some(choice), Some(other)) = (self.choice.as_mut(), other.choice.as_ref()) {
+            choice.select(other.selected());
+        }
+    }
+
+    /// Advances the current sentence's typewriter without pausing after punctuation, see
+    /// [`Paragraph::advance_typewriter`]
+    pub fn update_typewriter(&mut self, amount: f32, settings: &TypeWriterSettings) {
+        self.advance_typewriter(amount, settings, &TypeWriterPauses::default());
+    }
+    pub fn advance_typewriter(
+        &mut self,
+        amount: f32,
+        settings: &TypeWriterSettings,
+        pauses: &TypeWriterPauses,
+    ) {
+        let current = self.current_sentence;
+        self.sentences
+            .iter_mut()
+            .take(current)
+            .for_each(|sentence| sentence.tick_reveal(amount));
+
         if let Some(section) = self.get_current_sentence_mut() {
-            section.mut_typewriter().advance(amount);
+            section.advance_typewriter(amount, settings, pauses);
         }
     }
 
     pub fn all_paragraphs_visible(&self) -> bool {
-        self.current_sentence + 1 == self.sentences.len()
+        self.sentences
+            .iter()
+            .skip(self.current_sentence + 1)
+            .all(|sentence| sentence.is_skipped())
     }
     pub fn all_characters_displayed(&self) -> bool {
         self.get_current_sentence()
-            .is_some_and(|section| section.is_typewriter_finished())
+            .is_none_or(|section| section.is_skipped() || section.is_typewriter_finished())
+    }
+
+    /// Whether every sentence has been shown and the paragraph is waiting on the player to pick an option
+    /// ```
+    /// # use bevy_dialogue_system::prelude::*;
+    /// let paragraph = Paragraph::new()
+    ///     .with_sentences(vec![Sentence::new().with_text("Well?")])
+    ///     .with_choice(Choice::new().with_options(vec![ChoiceOption::new("Yes")]));
+    ///
+    /// assert!(paragraph.awaiting_choice());
+    /// ```
+    pub fn awaiting_choice(&self) -> bool {
+        self.choice
+            .as_ref()
+            .is_some_and(|choice| choice.has_visible_options())
+            && (self.sentences.is_empty()
+                || (self.all_paragraphs_visible() && self.all_characters_displayed()))
     }
 
     pub fn as_text_sections(&self) -> impl Iterator<Item = TextSection> + '_ {
         self.sentences
             .iter()
             .take(self.current_sentence + 1)
-            .map(|section| section.as_text_section())
+            .filter(|section| !section.is_skipped())
+            .flat_map(|section| section.as_text_sections())
+    }
+    /// How far each of [`Paragraph::as_text_sections`] is moved by its sentence's reveal effect, in character heights
+    pub fn reveal_offsets(&self) -> impl Iterator<Item = Vec2> + '_ {
+        self.sentences
+            .iter()
+            .take(self.current_sentence + 1)
+            .filter(|section| !section.is_skipped())
+            .flat_map(|section| section.reveal_offsets())
+    }
+    /// [`Paragraph::as_text_sections`] with their [`TextEffect`]s played `time` seconds in,
+    /// along with how far each is moved in character heights
+    pub fn animate<'a>(
+        &'a self,
+        time: f32,
+        settings: &'a TextEffectSettings,
+    ) -> impl Iterator<Item = (TextSection, Vec2)> + 'a {
+        self.sentences
+            .iter()
+            .take(self.current_sentence + 1)
+            .filter(|section| !section.is_skipped())
+            .flat_map(move |section| section.animate(time, settings))
     }
 }
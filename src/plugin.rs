@@ -1,79 +1,602 @@
-use crate::prelude::*;
-
-#[derive(Resource, Default)]
-pub struct CurrentAction(Option<fn(&mut World)>);
+use crate::{action::AfterWait, input::BindingInput, prelude::*};
+use bevy::{ecs::system::SystemParam, text::TextLayoutInfo};
 
+/// Shows and advances every [`Dialogue`], and loads dialogue files
+///
+/// Needs bevy's `AssetPlugin`, which `DefaultPlugins` includes, as dialogue files are loaded as assets.
+/// Typewriters and text effects are configured through the [`TypeWriterSettings`] and [`TextEffectSettings`] resources
+/// ```
+/// # use bevy_dialogue_system::prelude::*;
+/// # use bevy::prelude::*;
+/// let mut app = App::new();
+/// app.add_plugins((MinimalPlugins, AssetPlugin::default(), bevy::input::InputPlugin, DialoguePlugin))
+///     .insert_resource(TypeWriterSettings::characters_per_second(30.))
+///     .insert_resource(TextEffectSettings::reduced_motion());
+/// ```
+#[derive(Default)]
 pub struct DialoguePlugin;
 
 impl Plugin for DialoguePlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<CurrentAction>().add_systems(
-            Update,
-            (
-                advance_dialogue,
-                run_action,
-                show_dialogue,
-                update_typewriter,
-                update_dialogue,
-                move_dialogue,
-                change_width,
+        app.init_resource::<DialogueActions>()
+            .init_resource::<TypeWriterSettings>()
+            .init_resource::<TextEffectSettings>()
+            .init_resource::<DialogueVariables>()
+            .init_resource::<InkImporter>()
+            .init_resource::<DialogueCommands>()
+            .add_event::<DialogueInput>()
+            .add_event::<ResumeDialogue>()
+            .add_event::<DialogueStarted>()
+            .add_event::<ParagraphStarted>()
+            .add_event::<SentenceStarted>()
+            .add_event::<SentenceFinishedTyping>()
+            .add_event::<ParagraphFinished>()
+            .add_event::<DialogueEnded>()
+            .add_systems(
+                Update,
+                (
+                    load_dialogue_sources,
+                    reload_dialogue_sources,
+                    (
+                        read_bindings,
+                        resume_dialogue,
+                        navigate_choices,
+                        advance_dialogue,
+                        skip_dialogue,
+                        auto_advance_dialogue,
+                        wait_dialogue,
+                    )
+                        .chain(),
+                    run_actions,
+                    start_paragraphs,
+                    show_dialogue,
+                    update_typewriter,
+                    play_typewriter_voices,
+                    send_dialogue_events,
+                    run_actions,
+                    update_dialogue,
+                    despawn_choices,
+                    spawn_choices,
+                    highlight_choices,
+                    move_dialogue,
+                    change_width,
+                )
+                    .chain(),
             )
-                .chain(),
-        );
+            .add_systems(
+                PostUpdate,
+                offset_glyphs.after(bevy::ui::widget::text_system),
+            );
+    }
+
+    // Done once every plugin has been added so that `DialoguePlugin` can come before `AssetPlugin`, and so that the
+    // loaders share the `DialogueCommands` and `InkImporter` resources the app ends up with
+    fn finish(&self, app: &mut App) {
+        app.init_asset::<DialogueAsset>()
+            .init_asset_loader::<DialogueAssetLoader>()
+            .init_asset_loader::<YarnAssetLoader>()
+            .init_asset_loader::<InkAssetLoader>();
     }
 }
 
 fn advance_dialogue(
-    mut dialogue: Query<&mut Dialogue>,
-    mut current_action: ResMut<CurrentAction>,
-    input: Res<ButtonInput<KeyCode>>,
+    mut dialogue: Query<(Entity, &mut Dialogue)>,
+    mut actions: ResMut<DialogueActions>,
+    mut variables: ResMut<DialogueVariables>,
+    mut input: EventReader<DialogueInput>,
 ) {
+    let input = input.read().copied().collect::<Vec<_>>();
+
     dialogue
         .iter_mut()
-        // Ensure that the dialogue only updates when corresponding keys are pressed
-        .filter(|dialogue| input.any_just_pressed(dialogue.skip_keys().cloned()))
-        .filter(|dialogue| dialogue.get_current_paragraph().is_some())
-        .for_each(|mut dialogue| {
-            let front = dialogue.get_current_paragraph_mut().unwrap();
-
-            // Are all paragraphs in the current chapter shown
-            let all_sections = front.all_paragraphs_visible();
-            // Are all characters in the current sentence shown
-            let all_characters = front.all_characters_displayed();
-
-            current_action.0 = front
-                .get_current_sentence()
-                .and_then(|section| section.get_action().cloned());
-
-            if all_sections && all_characters {
-                dialogue.advance_paragraph();
-            } else if all_characters {
-                front.advance_sentence();
-            } else if let Some(section) = front.get_current_sentence_mut() {
-                section.mut_typewriter().finish();
+        // Ensure that the dialogue only updates when asked to advance
+        .filter(|(entity, _)| {
+            input
+                .iter()
+                .any(|input| input.is(*entity, DialogueAction::Advance))
+        })
+        .filter(|(_, dialogue)| {
+            dialogue
+                .get_current_paragraph()
+                .is_some_and(|paragraph| paragraph.is_started() && !paragraph.awaiting_choice())
+                && !dialogue.is_waiting()
+                && !dialogue.is_pausing()
+        })
+        .for_each(|(entity, mut dialogue)| {
+            dialogue.stop_skipping();
+            dialogue.set_auto(false);
+            advance(entity, &mut dialogue, &mut actions, &mut variables);
+        });
+}
+
+fn skip_dialogue(
+    mut dialogue: Query<(Entity, &mut Dialogue)>,
+    mut actions: ResMut<DialogueActions>,
+    mut variables: ResMut<DialogueVariables>,
+    mut input: EventReader<DialogueInput>,
+    time: Res<Time>,
+) {
+    let input = input.read().copied().collect::<Vec<_>>();
+
+    dialogue.iter_mut().for_each(|(entity, mut dialogue)| {
+        dialogue.set_fast_forward(
+            input
+                .iter()
+                .any(|input| input.is(entity, DialogueAction::FastForward)),
+        );
+        if input
+            .iter()
+            .any(|input| input.is(entity, DialogueAction::SkipAll))
+        {
+            if dialogue.is_skipping() {
+                dialogue.stop_skipping();
+            } else {
+                dialogue.start_skipping();
             }
+        }
+
+        if dialogue.update_skip(time.delta_seconds()) {
+            advance(entity, &mut dialogue, &mut actions, &mut variables);
+        }
+    });
+}
+
+fn auto_advance_dialogue(
+    mut dialogue: Query<(Entity, &mut Dialogue)>,
+    mut actions: ResMut<DialogueActions>,
+    mut variables: ResMut<DialogueVariables>,
+    mut input: EventReader<DialogueInput>,
+    time: Res<Time>,
+) {
+    let input = input.read().copied().collect::<Vec<_>>();
+
+    dialogue.iter_mut().for_each(|(entity, mut dialogue)| {
+        if input
+            .iter()
+            .any(|input| input.is(entity, DialogueAction::ToggleAuto))
+        {
+            let auto = !dialogue.is_auto();
+            dialogue.set_auto(auto);
+        }
+
+        if dialogue.update_auto(time.delta_seconds()) {
+            advance(entity, &mut dialogue, &mut actions, &mut variables);
+        }
+    });
+}
+
+fn wait_dialogue(
+    mut dialogue: Query<(Entity, &mut Dialogue)>,
+    mut actions: ResMut<DialogueActions>,
+    mut variables: ResMut<DialogueVariables>,
+    time: Res<Time>,
+) {
+    dialogue.iter_mut().for_each(|(entity, mut dialogue)| {
+        if dialogue.update_wait(time.delta_seconds()) {
+            advance(entity, &mut dialogue, &mut actions, &mut variables);
+        }
+    });
+}
+
+/// Finishes the typewriter, or moves on to the next sentence or paragraph
+fn advance(
+    entity: Entity,
+    dialogue: &mut Dialogue,
+    actions: &mut DialogueActions,
+    variables: &mut DialogueVariables,
+) {
+    // Only leaving the sentence runs its action, not finishing its typewriter
+    let leaving = dialogue
+        .get_current_paragraph()
+        .is_some_and(Paragraph::all_characters_displayed);
+    if leaving && queue_action(entity, dialogue, ActionTiming::Exit, actions) {
+        dialogue.block(AfterWait::Advance);
+        return;
+    }
+
+    move_on(dialogue, variables);
+}
+
+/// Advances without running the sentence's action
+fn move_on(dialogue: &mut Dialogue, variables: &mut DialogueVariables) {
+    let line = dialogue.current_line();
+    let front = dialogue.get_current_paragraph_mut().unwrap();
+
+    // Are all paragraphs in the current chapter shown
+    let all_sections = front.all_paragraphs_visible();
+    // Are all characters in the current sentence shown
+    let all_characters = front.all_characters_displayed();
+
+    let seen = front
+        .get_current_sentence()
+        .filter(|section| all_characters && !section.is_skipped())
+        .and(line);
+
+    let jump = front
+        .get_current_sentence()
+        .filter(|section| all_characters && !section.is_skipped())
+        .and_then(|section| section.get_jump())
+        .cloned();
+
+    if let Some(jump) = jump {
+        if !dialogue.goto(&jump) {
+            warn!("Could not jump to missing paragraph {jump:?}, continuing on instead");
+            dialogue.advance_paragraph();
+        }
+    } else if all_sections && all_characters {
+        dialogue.advance_paragraph();
+    } else if all_characters {
+        front.advance_visible_sentence(variables);
+    } else if let Some(section) = front.get_current_sentence_mut() {
+        section.mut_typewriter().finish();
+    }
+
+    if let Some(seen) = seen {
+        dialogue.mark_seen(seen);
+    }
+    dialogue.reset_timers();
+}
+
+/// Queues the action the current sentence runs at `timing`, returning whether the dialogue has to wait for it
+fn queue_action(
+    entity: Entity,
+    dialogue: &Dialogue,
+    timing: ActionTiming,
+    actions: &mut DialogueActions,
+) -> bool {
+    let Some(sentence) = dialogue
+        .get_current_paragraph()
+        .and_then(Paragraph::get_current_sentence)
+    else {
+        return false;
+    };
+    let Some(action) = sentence.action_on(timing) else {
+        return false;
+    };
+
+    if sentence.has_blocking_action() {
+        actions.push_blocking(entity, action);
+        true
+    } else {
+        actions.push(entity, action);
+        false
+    }
+}
+
+fn resume_dialogue(
+    mut dialogue: Query<(Entity, &mut Dialogue, Has<BlockingAction>)>,
+    mut resumed: EventReader<ResumeDialogue>,
+    mut removed: RemovedComponents<BlockingAction>,
+    mut commands: Commands,
+    mut variables: ResMut<DialogueVariables>,
+) {
+    let resumed = resumed
+        .read()
+        .map(|ResumeDialogue(entity)| (*entity, true))
+        .chain(removed.read().map(|entity| (entity, false)))
+        .collect::<Vec<_>>();
+
+    resumed.into_iter().for_each(|(entity, sent)| {
+        let Ok((_, mut dialogue, blocked)) = dialogue.get_mut(entity) else {
+            return;
+        };
+        // The marker may have been put back for another action since it was removed
+        if !sent && blocked {
+            return;
+        }
+        if blocked {
+            commands.entity(entity).remove::<BlockingAction>();
+        }
+
+        match dialogue.resume() {
+            Some(AfterWait::Advance) => move_on(&mut dialogue, &mut variables),
+            Some(AfterWait::Choose(index)) => dialogue.select_choice(index),
+            Some(AfterWait::Stay) | None => {}
+        }
+    });
+}
+
+fn load_dialogue_sources(
+    mut commands: Commands,
+    mut sources: Query<(Entity, &mut DialogueSource, Option<&mut Dialogue>)>,
+    assets: Res<Assets<DialogueAsset>>,
+) {
+    sources
+        .iter_mut()
+        .filter(|(_, source, _)| !source.is_loaded())
+        .for_each(|(entity, mut source, dialogue)| {
+            let Some(asset) = assets.get(source.handle()) else {
+                return;
+            };
+
+            match dialogue {
+                Some(mut dialogue) => dialogue.set_paragraphs(asset.paragraphs().clone()),
+                None => {
+                    commands
+                        .entity(entity)
+                        .insert(Dialogue::new().with_paragraphs(asset.paragraphs().clone()));
+                }
+            }
+            source.mark_loaded();
         });
 }
 
-fn run_action(world: &mut World) {
-    if let Some(action) = world.resource_mut::<CurrentAction>().0 {
-        world.run_system_once(action);
+fn reload_dialogue_sources(
+    mut events: EventReader<AssetEvent<DialogueAsset>>,
+    mut sources: Query<(&DialogueSource, &mut Dialogue)>,
+    assets: Res<Assets<DialogueAsset>>,
+) {
+    events.read().for_each(|event| {
+        let AssetEvent::Modified { id } = event else {
+            return;
+        };
+        let Some(asset) = assets.get(*id) else {
+            return;
+        };
+
+        sources
+            .iter_mut()
+            .filter(|(source, _)| source.is_loaded() && source.handle().id() == *id)
+            .for_each(|(_, mut dialogue)| {
+                if !dialogue.reload_paragraphs(asset.paragraphs().clone()) {
+                    info!("Dialogue changed too much to keep its place, starting again from the beginning");
+                }
+            });
+    });
+}
+
+fn navigate_choices(
+    mut dialogue: Query<(Entity, &mut Dialogue)>,
+    lists: Query<&ChoiceList>,
+    buttons: Query<(&ChoiceButton, &Interaction), Changed<Interaction>>,
+    mut actions: ResMut<DialogueActions>,
+    mut input: EventReader<DialogueInput>,
+) {
+    let input = input.read().copied().collect::<Vec<_>>();
+    let asked =
+        |entity: Entity, action: DialogueAction| input.iter().any(|input| input.is(entity, action));
+
+    dialogue
+        .iter_mut()
+        // Only react once the options are actually on screen
+        .filter(|(entity, dialogue)| {
+            lists.iter().any(|list| list.dialogue == *entity) && !dialogue.is_waiting()
+        })
+        .for_each(|(entity, mut dialogue)| {
+            let mut confirmed = asked(entity, DialogueAction::ChoiceConfirm);
+
+            let Some(choice) = dialogue.get_current_choice_mut() else {
+                return;
+            };
+
+            if asked(entity, DialogueAction::ChoiceUp) {
+                choice.select_previous();
+            }
+            if asked(entity, DialogueAction::ChoiceDown) {
+                choice.select_next();
+            }
+            buttons
+                .iter()
+                .filter(|(button, _)| button.dialogue == entity)
+                .for_each(|(button, interaction)| match interaction {
+                    Interaction::Pressed => {
+                        choice.select(button.index);
+                        confirmed = true;
+                    }
+                    Interaction::Hovered => choice.select(button.index),
+                    Interaction::None => {}
+                });
+
+            if confirmed {
+                let selected = choice.selected();
+
+                let blocked = queue_action(entity, &dialogue, ActionTiming::Exit, &mut actions);
+
+                if let Some(line) = dialogue.current_line() {
+                    dialogue.mark_seen(line);
+                }
+                if blocked {
+                    dialogue.block(AfterWait::Choose(selected));
+                } else {
+                    dialogue.select_choice(selected);
+                }
+            }
+        });
+}
+
+fn read_bindings(
+    dialogue: Query<(Entity, &Dialogue)>,
+    input: BindingInput,
+    mut events: EventWriter<DialogueInput>,
+) {
+    dialogue.iter().for_each(|(entity, dialogue)| {
+        dialogue
+            .bindings()
+            .iter()
+            .map(|(action, _)| *action)
+            .filter(|action| input.triggers(dialogue.bindings(), *action))
+            .for_each(|action| {
+                events.send(DialogueInput::to(entity, action));
+            });
+    });
+}
+
+fn run_actions(world: &mut World) {
+    while let Some((dialogue, action, blocking)) = world.resource_mut::<DialogueActions>().next() {
+        if let Some(mut entity) = world.get_entity_mut(dialogue).filter(|_| blocking) {
+            entity.insert(BlockingAction);
+        }
+        action.run(dialogue, world);
     }
-    world.resource_mut::<CurrentAction>().0 = None;
 }
 
-fn update_dialogue(mut dialogue_area: Query<(&mut Text, &Dialogue)>) {
+fn start_paragraphs(
+    mut dialogue: Query<(Entity, &mut Dialogue)>,
+    mut variables: ResMut<DialogueVariables>,
+    mut actions: ResMut<DialogueActions>,
+) {
+    dialogue.iter_mut().for_each(|(entity, mut dialogue)| {
+        dialogue.start_paragraph(&mut variables);
+        dialogue
+            .take_started_commands()
+            .into_iter()
+            .for_each(|command| actions.push(entity, SentenceAction::Command(command)));
+    });
+}
+
+fn update_dialogue(
+    mut dialogue_area: Query<(&mut Text, &Dialogue)>,
+    time: Res<Time>,
+    settings: Res<TextEffectSettings>,
+) {
     dialogue_area.iter_mut().for_each(|(mut text, dialogue)| {
         if let Some(paragraph) = dialogue.get_current_paragraph() {
-            text.sections = paragraph.as_text_sections().collect();
+            text.sections = paragraph
+                .animate(time.elapsed_seconds(), &settings)
+                .map(|(section, _)| section)
+                .collect();
         }
     });
 }
 
-fn update_typewriter(mut dialogue: Query<&mut Dialogue>, time: Res<Time>) {
+fn update_typewriter(
+    mut dialogue: Query<&mut Dialogue>,
+    time: Res<Time>,
+    settings: Res<TypeWriterSettings>,
+) {
+    dialogue
+        .iter_mut()
+        .for_each(|mut dialogue| dialogue.advance_typewriter(time.delta_seconds(), &settings));
+}
+
+fn play_typewriter_voices(mut commands: Commands, mut dialogue: Query<&mut Dialogue>) {
     dialogue.iter_mut().for_each(|mut dialogue| {
-        if let Some(paragraph) = dialogue.get_current_paragraph_mut() {
-            paragraph.update_typewriter(time.delta_seconds());
+        let Some(blip) = dialogue
+            .get_current_paragraph_mut()
+            .and_then(|paragraph| paragraph.get_current_sentence_mut())
+            .and_then(|sentence| sentence.speak())
+        else {
+            return;
+        };
+
+        commands.spawn(AudioBundle {
+            source: blip.sound,
+            settings: PlaybackSettings::DESPAWN
+                .with_speed(blip.pitch)
+                .with_volume(bevy::audio::Volume::new(blip.volume)),
+        });
+    });
+}
+
+// Moves glyphs for reveal and text effects, which can only be done once the text has been laid out.
+// That happens again whenever the text changes, so the offsets never build up
+fn offset_glyphs(
+    mut dialogue_area: Query<(&mut TextLayoutInfo, &Dialogue), Changed<TextLayoutInfo>>,
+    time: Res<Time>,
+    settings: Res<TextEffectSettings>,
+) {
+    dialogue_area.iter_mut().for_each(|(mut layout, dialogue)| {
+        let Some(paragraph) = dialogue.get_current_paragraph() else {
+            return;
+        };
+        let offsets = paragraph
+            .animate(time.elapsed_seconds(), &settings)
+            .map(|(_, offset)| offset)
+            .collect::<Vec<_>>();
+        if offsets.iter().all(|offset| *offset == Vec2::ZERO) {
+            return;
+        }
+
+        layout.glyphs.iter_mut().for_each(|glyph| {
+            if let Some(offset) = offsets.get(glyph.section_index) {
+                glyph.position += *offset * glyph.size.y;
+            }
+        });
+    });
+}
+
+#[derive(SystemParam)]
+struct LifecycleEvents<'w> {
+    dialogue_started: EventWriter<'w, DialogueStarted>,
+    paragraph_started: EventWriter<'w, ParagraphStarted>,
+    sentence_started: EventWriter<'w, SentenceStarted>,
+    sentence_typed: EventWriter<'w, SentenceFinishedTyping>,
+    paragraph_finished: EventWriter<'w, ParagraphFinished>,
+    dialogue_ended: EventWriter<'w, DialogueEnded>,
+}
+
+// Compares where each dialogue is with where it was last frame, so that however it got there the events are the same
+fn send_dialogue_events(
+    mut dialogue: Query<(Entity, &mut Dialogue)>,
+    mut events: LifecycleEvents,
+    mut actions: ResMut<DialogueActions>,
+) {
+    dialogue.iter_mut().for_each(|(entity, mut dialogue)| {
+        let now = dialogue.progress();
+        if now.is_some()
+            && !dialogue
+                .get_current_paragraph()
+                .is_some_and(Paragraph::is_started)
+        {
+            return;
+        }
+        let before = dialogue.report(now.clone());
+
+        let paragraph_changed = match (&before, &now) {
+            (None, None) => return,
+            (Some(before), Some(now)) => before.visits != now.visits,
+            _ => true,
+        };
+
+        if let Some(before) = before.as_ref().filter(|_| paragraph_changed) {
+            events.paragraph_finished.send(ParagraphFinished {
+                dialogue: entity,
+                paragraph: before.paragraph,
+                name: before.name.clone(),
+                in_branch: before.in_branch,
+            });
+        }
+        let Some(now) = now else {
+            events
+                .dialogue_ended
+                .send(DialogueEnded { dialogue: entity });
+            return;
+        };
+
+        if before.is_none() {
+            events
+                .dialogue_started
+                .send(DialogueStarted { dialogue: entity });
+        }
+        if paragraph_changed {
+            events.paragraph_started.send(ParagraphStarted {
+                dialogue: entity,
+                paragraph: now.paragraph,
+                name: now.name.clone(),
+                in_branch: now.in_branch,
+            });
+        }
+        let same_sentence = !paragraph_changed
+            && before
+                .as_ref()
+                .is_some_and(|before| before.sentence == now.sentence);
+        if !same_sentence {
+            events.sentence_started.send(SentenceStarted {
+                dialogue: entity,
+                paragraph: now.paragraph,
+                sentence: now.sentence,
+            });
+
+            if queue_action(entity, &dialogue, ActionTiming::Enter, &mut actions) {
+                dialogue.block(AfterWait::Stay);
+            }
+        }
+        if now.typed && !(same_sentence && before.is_some_and(|before| before.typed)) {
+            events.sentence_typed.send(SentenceFinishedTyping {
+                dialogue: entity,
+                paragraph: now.paragraph,
+                sentence: now.sentence,
+            });
         }
     });
 }
@@ -115,3 +638,101 @@ fn change_width(mut dialogue_area: Query<(&mut Style, &Dialogue)>) {
         }
     });
 }
+
+fn spawn_choices(
+    mut commands: Commands,
+    dialogue: Query<(Entity, &Dialogue, Option<&Parent>)>,
+    lists: Query<&ChoiceList>,
+    children: Query<&Children>,
+) {
+    dialogue
+        .iter()
+        .filter(|(entity, ..)| !lists.iter().any(|list| list.dialogue == *entity))
+        .for_each(|(entity, dialogue, parent)| {
+            let Some(choice) = dialogue.get_current_choice() else {
+                return;
+            };
+
+            let list = commands
+                .spawn(NodeBundle {
+                    style: choice.style().clone(),
+                    ..Default::default()
+                })
+                .insert(ChoiceList { dialogue: entity })
+                .with_children(|list| {
+                    choice
+                        .options()
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, option)| !option.is_hidden())
+                        .for_each(|(index, option)| {
+                            list.spawn(ButtonBundle {
+                                background_color: Color::NONE.into(),
+                                ..Default::default()
+                            })
+                            .insert(ChoiceButton {
+                                dialogue: entity,
+                                index,
+                            })
+                            .with_children(|button| {
+                                button.spawn(TextBundle::from_sections(
+                                    option.label().as_text_sections(),
+                                ));
+                            });
+                        })
+                })
+                .id();
+
+            // Keep the options next to the dialogue they belong to
+            if let Some(parent) = parent {
+                let position = children
+                    .get(parent.get())
+                    .ok()
+                    .and_then(|children| children.iter().position(|child| *child == entity))
+                    .map_or(0, |position| position + 1);
+
+                commands
+                    .entity(parent.get())
+                    .insert_children(position, &[list]);
+            }
+        });
+}
+
+fn despawn_choices(
+    mut commands: Commands,
+    lists: Query<(Entity, &ChoiceList)>,
+    dialogue: Query<&Dialogue>,
+) {
+    lists
+        .iter()
+        .filter(|(_, list)| {
+            dialogue
+                .get(list.dialogue)
+                .map_or(true, |dialogue| dialogue.get_current_choice().is_none())
+        })
+        .for_each(|(entity, _)| commands.entity(entity).despawn_recursive());
+}
+
+fn highlight_choices(
+    mut buttons: Query<(&ChoiceButton, &mut BackgroundColor)>,
+    dialogue: Query<&Dialogue>,
+) {
+    buttons
+        .iter_mut()
+        .for_each(|(button, mut background_color)| {
+            let Some(choice) = dialogue
+                .get(button.dialogue)
+                .ok()
+                .and_then(|dialogue| dialogue.get_current_choice())
+            else {
+                return;
+            };
+
+            *background_color = if choice.selected() == button.index {
+                *choice.highlight()
+            } else {
+                Color::NONE
+            }
+            .into();
+        });
+}
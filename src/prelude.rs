@@ -1,8 +1,31 @@
 pub use crate::{
-    dialogue::Dialogue,
+    action::{ActionClosure, ActionTiming, BlockingAction, DialogueActions, SentenceAction},
+    asset::{
+        ChoiceDescriptor, ChoiceOptionDescriptor, ColorDescriptor, DialogueAsset,
+        DialogueAssetError, DialogueAssetLoader, DialogueDescriptor, DialogueSource,
+        ParagraphDescriptor, SentenceDescriptor, TypeWriterDescriptor,
+    },
+    choice::{Choice, ChoiceButton, ChoiceList, ChoiceOption},
+    command::{CommandArgument, CommandError, CommandInput, DialogueCommand, DialogueCommands},
+    condition::{Comparison, ConditionError, DialogueCondition},
+    dialogue::{AutoAdvance, Dialogue},
+    effect::{TextEffect, TextEffectSettings},
+    event::{
+        DialogueEnded, DialogueStarted, ParagraphFinished, ParagraphStarted, ResumeDialogue,
+        SentenceFinishedTyping, SentenceStarted,
+    },
+    ink::{InkAssetLoader, InkDiagnostic, InkError, InkImporter},
+    input::{DialogueAction, DialogueBindings, DialogueInput, InputBinding},
+    markup::{MarkupSpan, TypeWriterCue},
     paragraph::Paragraph,
-    plugin::{CurrentAction, DialoguePlugin},
-    sentence::{Sentence, TypeWriter},
+    plugin::DialoguePlugin,
+    reveal::TypeWriterReveal,
+    sentence::{Sentence, TypeWriter, TypeWriterMode, TypeWriterPauses, TypeWriterSettings},
+    variables::{DialogueValue, DialogueVariables, VariableChange},
+    voice::{TypeWriterVoice, VoiceBlip},
+    wait::DialogueWait,
+    yarn::{parse_yarn, parse_yarn_with, YarnAssetLoader, YarnDiagnostic, YarnError},
 };
+pub(crate) use bevy::utils::{HashMap, HashSet};
 pub(crate) use bevy::{ecs::system::RunSystemOnce, prelude::*};
 pub(crate) use std::{collections::VecDeque, fmt::Display};
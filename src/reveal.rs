@@ -0,0 +1,116 @@
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::f32::consts::PI;
+
+/// How characters animate in as the typewriter reveals them, each taking `duration` seconds
+///
+/// The effects can be combined. UI text can't scale a single character, so `pop` springs it up and back into place
+/// ```
+/// # use bevy_dialogue_system::prelude::*;
+/// # use bevy::prelude::*;
+/// let settings = TypeWriterSettings::characters_per_second(10.);
+/// let mut sentence = Sentence::new()
+///     .with_text("Hey")
+///     .with_typewriter(TypeWriter::new().with_reveal(TypeWriterReveal::fade(0.2).with_slide(0.5)));
+///
+/// sentence.advance_typewriter(0.15, &settings, &TypeWriterPauses::empty());
+/// sentence.advance_typewriter(0.1, &settings, &TypeWriterPauses::empty());
+///
+/// let sections = sentence.as_text_sections();
+/// assert_eq!(sections.len(), 2);
+/// assert_eq!(sections[0].value, "H");
+/// assert!((sections[0].style.color.a() - 0.5).abs() < 0.001);
+/// assert_eq!(sections[1].value, "e");
+/// assert_eq!(sections[1].style.color.a(), 0.);
+/// assert_eq!(sentence.reveal_offsets()[1].y, 0.5);
+///
+/// sentence.advance_typewriter(0.1, &settings, &TypeWriterPauses::empty());
+/// assert_eq!(sentence.to_string(), "Hey");
+///
+/// sentence.advance_typewriter(0.3, &settings, &TypeWriterPauses::empty());
+/// assert_eq!(sentence.as_text_sections().len(), 1);
+/// assert_eq!(sentence.reveal_offsets(), vec![Vec2::ZERO]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TypeWriterReveal {
+    pub duration: f32,
+    /// Whether characters fade in from transparent
+    pub fade: bool,
+    /// How high characters spring, in character heights
+    pub pop: f32,
+    /// How far below their place characters start, in character heights
+    pub slide: f32,
+}
+impl Default for TypeWriterReveal {
+    fn default() -> Self {
+        Self {
+            duration: 0.2,
+            fade: false,
+            pop: 0.,
+            slide: 0.,
+        }
+    }
+}
+impl TypeWriterReveal {
+    pub fn fade(duration: f32) -> Self {
+        Self {
+            duration,
+            fade: true,
+            ..Default::default()
+        }
+    }
+    pub fn pop(duration: f32) -> Self {
+        Self {
+            duration,
+            pop: 0.25,
+            ..Default::default()
+        }
+    }
+    pub fn slide(duration: f32) -> Self {
+        Self {
+            duration,
+            slide: 0.5,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_duration(mut self, duration: f32) -> Self {
+        self.duration = duration;
+        self
+    }
+    pub fn with_fade(mut self, fade: bool) -> Self {
+        self.fade = fade;
+        self
+    }
+    pub fn with_pop(mut self, height: f32) -> Self {
+        self.pop = height;
+        self
+    }
+    pub fn with_slide(mut self, distance: f32) -> Self {
+        self.slide = distance;
+        self
+    }
+
+    /// How far along a character revealed `elapsed` seconds ago is, from 0 to 1
+    pub(crate) fn progress(&self, elapsed: f32) -> f32 {
+        if self.duration > 0. {
+            (elapsed / self.duration).clamp(0., 1.)
+        } else {
+            1.
+        }
+    }
+    pub(crate) fn alpha(&self, progress: f32) -> f32 {
+        if self.fade {
+            progress
+        } else {
+            1.
+        }
+    }
+    /// Downwards being positive, as in UI text layout
+    pub(crate) fn offset(&self, progress: f32) -> Vec2 {
+        let eased = 1. - (1. - progress).powi(2);
+
+        Vec2::Y * (self.slide * (1. - eased) - self.pop * (PI * progress).sin())
+    }
+}
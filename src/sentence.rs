@@ -1,19 +1,121 @@
-use crate::prelude::*;
+use crate::{markup::parse_markup, prelude::*};
+use unicode_segmentation::UnicodeSegmentation;
 
-#[derive(Debug)]
-pub struct TypeWriter {
-    active: bool,
-    time: f32,
-    speed: f32,
+/// What a typewriter's speed is measured in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum TypeWriterMode {
+    /// The fraction of the sentence revealed each second, whatever its length
+    #[default]
+    Normalized,
+    CharactersPerSecond,
+    WordsPerSecond,
+}
+
+/// The mode and speed used by typewriters that don't set their own, insert it as a resource to change them
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct TypeWriterSettings {
+    pub mode: TypeWriterMode,
+    pub speed: f32,
 }
-impl Default for TypeWriter {
+impl Default for TypeWriterSettings {
     fn default() -> Self {
+        Self::normalized(0.5)
+    }
+}
+impl TypeWriterSettings {
+    pub fn normalized(speed: f32) -> Self {
         Self {
-            active: false,
-            time: 0.,
-            speed: 0.5,
+            mode: TypeWriterMode::Normalized,
+            speed,
         }
     }
+    pub fn characters_per_second(speed: f32) -> Self {
+        Self {
+            mode: TypeWriterMode::CharactersPerSecond,
+            speed,
+        }
+    }
+    pub fn words_per_second(speed: f32) -> Self {
+        Self {
+            mode: TypeWriterMode::WordsPerSecond,
+            speed,
+        }
+    }
+}
+
+/// How long, in seconds, the typewriter waits after revealing punctuation
+///
+/// Only the last of a run of punctuation waits, so an ellipsis written as `...` waits once
+/// ```
+/// # use bevy_dialogue_system::prelude::*;
+/// let pauses = TypeWriterPauses::default().with(',', 0.3).without('!');
+///
+/// assert_eq!(pauses.get(','), Some(0.3));
+/// assert_eq!(pauses.get('!'), None);
+/// assert!(TypeWriterPauses::empty().get('.').is_none());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeWriterPauses(HashMap<char, f32>);
+
+impl Default for TypeWriterPauses {
+    fn default() -> Self {
+        Self::empty()
+            .with(',', 0.15)
+            .with(';', 0.15)
+            .with(':', 0.15)
+            .with('.', 0.35)
+            .with('!', 0.35)
+            .with('?', 0.35)
+            .with('…', 0.5)
+            .with('—', 0.3)
+    }
+}
+impl TypeWriterPauses {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn empty() -> Self {
+        Self(HashMap::new())
+    }
+
+    pub fn with(mut self, punctuation: char, seconds: f32) -> Self {
+        self.set(punctuation, seconds);
+        self
+    }
+    pub fn without(mut self, punctuation: char) -> Self {
+        self.remove(punctuation);
+        self
+    }
+    pub fn set(&mut self, punctuation: char, seconds: f32) {
+        self.0.insert(punctuation, seconds.max(0.));
+    }
+    pub fn remove(&mut self, punctuation: char) -> Option<f32> {
+        self.0.remove(&punctuation)
+    }
+    pub fn get(&self, punctuation: char) -> Option<f32> {
+        self.0.get(&punctuation).copied()
+    }
+    pub fn iter(&self) -> impl Iterator<Item = (&char, &f32)> {
+        self.0.iter()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TypeWriter {
+    active: bool,
+    time: f32,
+    speed: Option<f32>,
+    mode: Option<TypeWriterMode>,
+    /// When each character is revealed, as a fraction of the time the whole sentence takes
+    schedule: Vec<f32>,
+    voice: Option<TypeWriterVoice>,
+    /// How many characters the voice has gone past, and how many of those were voiced
+    spoken: usize,
+    voiced: usize,
+    reveal: Option<TypeWriterReveal>,
+    /// Seconds the typewriter has run for, and when each character was revealed
+    clock: f32,
+    revealed_at: Vec<f32>,
 }
 impl TypeWriter {
     pub fn new() -> Self {
@@ -32,21 +134,92 @@ impl TypeWriter {
         self.active
     }
 
+    /// Moves the fraction of the sentence revealed along by `amount * speed`, whatever the mode, using the speed of
+    /// `settings` when the typewriter doesn't have its own
     /// ```rust
     /// # use bevy_dialogue_system::prelude::*;
+    /// let settings = TypeWriterSettings::default();
     /// let mut typewriter = TypeWriter::new();
     ///
     /// assert_eq!(typewriter.current_time(), 0.);
     ///
-    /// typewriter.advance(0.1);
+    /// typewriter.advance(0.1, &settings);
     ///
     /// assert_eq!(typewriter.current_time(), 0.05);
     /// ```
-    pub fn advance(&mut self, amount: f32) {
-        self.time += amount * self.speed;
+    pub fn advance(&mut self, amount: f32, settings: &TypeWriterSettings) {
+        self.time += amount * self.speed(settings);
 
         self.time = self.time.clamp(0., 1.);
     }
+    /// Moves the typewriter along `amount` seconds through `text`, using `settings` for a missing mode or speed
+    ///
+    /// Every character takes as long as the others, see [`Sentence::advance_typewriter`] for pauses
+    /// ```rust
+    /// # use bevy_dialogue_system::prelude::*;
+    /// let settings = TypeWriterSettings::characters_per_second(10.);
+    ///
+    /// let mut short = TypeWriter::new();
+    /// short.advance_text(0.5, "Hello", &settings);
+    /// assert_eq!(short.current_time(), 1.);
+    ///
+    /// let mut long = TypeWriter::new();
+    /// long.advance_text(0.5, "Hello there, how are you?", &settings);
+    /// assert_eq!(long.current_time(), 0.2);
+    ///
+    /// let mut words = TypeWriter::new().with_mode(TypeWriterMode::WordsPerSecond).with_speed(2.);
+    /// words.advance_text(1., "Hello there, how are you?", &settings);
+    /// assert_eq!(words.current_time(), 0.4);
+    /// ```
+    pub fn advance_text(&mut self, amount: f32, text: &str, settings: &TypeWriterSettings) {
+        let characters = text.graphemes(true).count();
+        let duration = self.character_duration(text, settings) * characters as f32;
+
+        self.schedule.clear();
+        self.advance_seconds(amount, duration);
+    }
+    /// Moves the typewriter along `amount` seconds when the whole sentence takes `duration` seconds
+    pub fn advance_seconds(&mut self, amount: f32, duration: f32) {
+        if duration <= 0. {
+            self.finish();
+        } else {
+            self.time = (self.time + amount / duration).clamp(0., 1.);
+        }
+    }
+    /// How long each character of `text` takes to reveal, before any pauses
+    pub fn character_duration(&self, text: &str, settings: &TypeWriterSettings) -> f32 {
+        let speed = self.speed(settings);
+        let characters = text.graphemes(true).count().max(1) as f32;
+
+        let per_second = match self.mode.unwrap_or(settings.mode) {
+            TypeWriterMode::Normalized => speed * characters,
+            TypeWriterMode::CharactersPerSecond => speed,
+            TypeWriterMode::WordsPerSecond => {
+                speed * characters / text.unicode_words().count().max(1) as f32
+            }
+        };
+
+        if per_second > 0. {
+            1. / per_second
+        } else {
+            f32::INFINITY
+        }
+    }
+    /// When each character is revealed, as a fraction of the whole sentence, rather than evenly spaced
+    pub fn set_schedule(&mut self, schedule: Vec<f32>) {
+        self.schedule = schedule;
+    }
+    pub fn schedule(&self) -> &[f32] {
+        &self.schedule
+    }
+    /// How many of `characters` have been revealed
+    pub fn revealed(&self, characters: usize) -> usize {
+        if self.schedule.len() == characters {
+            self.schedule.partition_point(|reveal| *reveal <= self.time)
+        } else {
+            (self.time * characters as f32) as usize
+        }
+    }
     /// ```rust
     /// # use bevy_dialogue_system::prelude::*;
     /// let typewriter = TypeWriter::new().with_time(0.5);
@@ -65,34 +238,129 @@ impl TypeWriter {
     }
     pub fn reset(&mut self) {
         self.time = 0.;
+        self.spoken = 0;
+        self.voiced = 0;
+        self.clock = 0.;
+        self.revealed_at.clear();
     }
     pub fn current_time(&self) -> f32 {
         self.time
     }
 
+    /// Measured in the typewriter's mode, which is the one in [`TypeWriterSettings`] if it doesn't have its own
     pub fn with_speed(mut self, speed: f32) -> Self {
-        self.speed = speed.clamp(0., 1.);
+        self.set_speed(speed);
         self
     }
     pub fn set_speed(&mut self, speed: f32) {
-        self.speed = speed.clamp(0., 1.);
+        self.speed = Some(speed.max(0.));
+    }
+    pub fn reset_speed(&mut self) {
+        self.speed = None;
     }
-    pub fn speed(&self) -> f32 {
+    /// The typewriter's own speed, or the one of `settings`, which is usually the [`TypeWriterSettings`] resource
+    pub fn speed(&self, settings: &TypeWriterSettings) -> f32 {
+        self.speed.unwrap_or(settings.speed)
+    }
+    /// The typewriter's own speed, `None` when it uses the one of [`TypeWriterSettings`]
+    pub fn speed_override(&self) -> Option<f32> {
         self.speed
     }
+
+    pub fn with_mode(mut self, mode: TypeWriterMode) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+    pub fn set_mode(&mut self, mode: TypeWriterMode) {
+        self.mode = Some(mode);
+    }
+    pub fn reset_mode(&mut self) {
+        self.mode = None;
+    }
+    pub fn mode(&self) -> Option<TypeWriterMode> {
+        self.mode
+    }
+
+    pub fn with_voice(mut self, voice: TypeWriterVoice) -> Self {
+        self.voice = Some(voice);
+        self
+    }
+    pub fn set_voice(&mut self, voice: TypeWriterVoice) {
+        self.voice = Some(voice);
+    }
+    pub fn remove_voice(&mut self) {
+        self.voice = None;
+    }
+    pub fn voice(&self) -> Option<&TypeWriterVoice> {
+        self.voice.as_ref()
+    }
+    pub fn voice_mut(&mut self) -> Option<&mut TypeWriterVoice> {
+        self.voice.as_mut()
+    }
+
+    pub fn with_reveal(mut self, reveal: TypeWriterReveal) -> Self {
+        self.reveal = Some(reveal);
+        self
+    }
+    pub fn set_reveal(&mut self, reveal: TypeWriterReveal) {
+        self.reveal = Some(reveal);
+    }
+    pub fn remove_reveal(&mut self) {
+        self.reveal = None;
+    }
+    pub fn reveal(&self) -> Option<&TypeWriterReveal> {
+        self.reveal.as_ref()
+    }
+    /// Runs the reveal effect's clock on `amount` seconds, once `revealed` characters are showing
+    pub(crate) fn tick_reveal(&mut self, amount: f32, revealed: usize) {
+        self.clock += amount;
+        self.revealed_at.truncate(revealed);
+        self.revealed_at.resize(revealed, self.clock);
+    }
+    /// How far the character at `index` is through its reveal effect, from 0 to 1
+    pub(crate) fn reveal_progress(&self, index: usize) -> f32 {
+        match (&self.reveal, self.revealed_at.get(index)) {
+            (Some(reveal), Some(revealed_at)) => reveal.progress(self.clock - revealed_at),
+            _ => 1.,
+        }
+    }
+
+    pub fn characters_per_second(speed: f32) -> Self {
+        Self::new()
+            .with_mode(TypeWriterMode::CharactersPerSecond)
+            .with_speed(speed)
+    }
+    pub fn words_per_second(speed: f32) -> Self {
+        Self::new()
+            .with_mode(TypeWriterMode::WordsPerSecond)
+            .with_speed(speed)
+    }
 }
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone)]
 pub struct Sentence {
     text_section: TextSection,
-    action: Option<fn(&mut World)>,
+    speaker: Option<String>,
+    line_id: Option<String>,
+    action: Option<SentenceAction>,
+    action_timing: ActionTiming,
+    blocking: bool,
+    wait: Option<DialogueWait>,
+    jump: Option<String>,
+    condition: Option<DialogueCondition>,
+    variable_changes: Vec<VariableChange>,
+    skipped: bool,
     typewriter: TypeWriter,
+    spans: Vec<MarkupSpan>,
+    cues: Vec<(usize, TypeWriterCue)>,
+    bold_font: Option<Handle<Font>>,
+    effects: Vec<TextEffect>,
 }
 
 impl Display for Sentence {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let str = if let Some(characters) = self.typewriter_characters() {
-            self.text_section.value[0..characters].to_string()
+        let str = if let Some(bytes) = self.typewriter_bytes() {
+            self.text_section.value[0..bytes].to_string()
         } else {
             self.text_section.value.to_string()
         };
@@ -115,11 +383,13 @@ impl Sentence {
     }
 
     pub fn with_text(mut self, value: impl ToString) -> Self {
-        self.text_section.value = value.to_string();
+        self.set_text(value);
         self
     }
     pub fn set_text(&mut self, value: impl ToString) {
         self.text_section.value = value.to_string();
+        self.spans.clear();
+        self.cues.clear();
     }
     pub fn text(&self) -> &str {
         &self.text_section.value
@@ -128,6 +398,73 @@ impl Sentence {
         &mut self.text_section.value
     }
 
+    /// Who says the line, kept apart from the text so that it can be shown in its own place such as a name box
+    pub fn with_speaker(mut self, speaker: impl ToString) -> Self {
+        self.set_speaker(speaker);
+        self
+    }
+    pub fn remove_speaker(mut self) -> Self {
+        self.speaker = None;
+        self
+    }
+    pub fn set_speaker(&mut self, speaker: impl ToString) {
+        self.speaker = Some(speaker.to_string());
+    }
+    pub fn speaker(&self) -> Option<&str> {
+        self.speaker.as_deref()
+    }
+
+    /// A name for the line that stays the same when the dialogue around it changes, which is what
+    /// [`Dialogue::has_seen`] remembers it by instead of where it is
+    pub fn with_line_id(mut self, id: impl ToString) -> Self {
+        self.set_line_id(id);
+        self
+    }
+    pub fn set_line_id(&mut self, id: impl ToString) {
+        self.line_id = Some(id.to_string());
+    }
+    pub fn line_id(&self) -> Option<&str> {
+        self.line_id.as_deref()
+    }
+
+    /// Sets the text from markup, styling parts of it with `[color=red]`, `[size=64]` and `[b]` tags,
+    /// pausing the typewriter with `{wait=0.5}` and changing its speed with `{speed=2}`
+    ///
+    /// Colors are either a name or a hex code, `[[` is written as a literal `[` and `{{` as a literal `{`.
+    /// The typewriter reveals the whole sentence as one, whatever the styles
+    /// ```
+    /// # use bevy_dialogue_system::prelude::*;
+    /// # use bevy::prelude::*;
+    /// let sentence = Sentence::new()
+    ///     .with_markup("Hello [color=red]world[/color] [size=64]big[/size] [[b]")
+    ///     .with_typewriter(TypeWriter::new().with_time(0.5));
+    ///
+    /// assert_eq!(sentence.text(), "Hello world big [b]");
+    /// assert_eq!(sentence.spans().len(), 2);
+    ///
+    /// let sections = sentence.as_text_sections();
+    /// assert_eq!(sections[0].value, "Hello ");
+    /// assert_eq!(sections[1].value, "wor");
+    /// assert_eq!(sections[1].style.color, Color::RED);
+    /// ```
+    pub fn with_markup(mut self, markup: &str) -> Self {
+        self.set_markup(markup);
+        self
+    }
+    pub fn set_markup(&mut self, markup: &str) {
+        let markup = parse_markup(markup);
+        self.text_section.value = markup.text;
+        self.spans = markup.spans;
+        self.cues = markup.cues;
+    }
+    pub fn spans(&self) -> &[MarkupSpan] {
+        &self.spans
+    }
+    /// Typewriter cues from the markup, with the byte offset into the text they come before
+    pub fn cues(&self) -> &[(usize, TypeWriterCue)] {
+        &self.cues
+    }
+
     pub fn with_font(mut self, font: Handle<Font>) -> Self {
         self.text_section.style.font = font;
         self
@@ -142,6 +479,35 @@ impl Sentence {
         &mut self.text_section.style.font
     }
 
+    /// The font used for `[b]` markup, without one bold text uses the normal font
+    pub fn with_bold_font(mut self, font: Handle<Font>) -> Self {
+        self.bold_font = Some(font);
+        self
+    }
+    pub fn set_bold_font(&mut self, font: Handle<Font>) {
+        self.bold_font = Some(font);
+    }
+    pub fn bold_font(&self) -> Option<&Handle<Font>> {
+        self.bold_font.as_ref()
+    }
+
+    /// Plays the effect across the whole sentence, on top of any from its markup
+    pub fn with_effect(mut self, effect: TextEffect) -> Self {
+        self.add_effect(effect);
+        self
+    }
+    pub fn add_effect(&mut self, effect: TextEffect) {
+        if !self.effects.contains(&effect) {
+            self.effects.push(effect);
+        }
+    }
+    pub fn remove_effect(&mut self, effect: TextEffect) {
+        self.effects.retain(|other| *other != effect);
+    }
+    pub fn effects(&self) -> &[TextEffect] {
+        &self.effects
+    }
+
     pub fn with_font_size(mut self, size: f32) -> Self {
         self.text_section.style.font_size = size;
         self
@@ -171,6 +537,16 @@ impl Sentence {
     }
 
     pub fn with_action(mut self, action: fn(&mut World)) -> Self {
+        self.action = Some(SentenceAction::World(action));
+        self
+    }
+    /// An action that is given the entity of the dialogue the sentence belongs to
+    pub fn with_dialogue_action(mut self, action: fn(In<Entity>, &mut World)) -> Self {
+        self.action = Some(SentenceAction::WithDialogue(action));
+        self
+    }
+    /// A registered system or closure, see [`SentenceAction`]
+    pub fn with_sentence_action(mut self, action: SentenceAction) -> Self {
         self.action = Some(action);
         self
     }
@@ -179,14 +555,211 @@ impl Sentence {
         self
     }
     pub fn set_action(&mut self, action: fn(&mut World)) {
+        self.action = Some(SentenceAction::World(action));
+    }
+    pub fn set_sentence_action(&mut self, action: SentenceAction) {
         self.action = Some(action);
     }
-    pub fn get_action(&self) -> Option<&fn(&mut World)> {
+    pub fn get_action(&self) -> Option<&SentenceAction> {
         self.action.as_ref()
     }
-    pub fn get_action_mut(&mut self) -> Option<&mut fn(&mut World)> {
+    pub fn get_action_mut(&mut self) -> Option<&mut SentenceAction> {
         self.action.as_mut()
     }
+    /// Whether the action runs when the sentence is shown or when the dialogue moves on from it, which is the default
+    pub fn with_action_timing(mut self, timing: ActionTiming) -> Self {
+        self.action_timing = timing;
+        self
+    }
+    pub fn set_action_timing(&mut self, timing: ActionTiming) {
+        self.action_timing = timing;
+    }
+    pub fn action_timing(&self) -> ActionTiming {
+        self.action_timing
+    }
+    /// Whether the dialogue waits for the action to finish before showing anything else, ignoring the player
+    /// until [`ResumeDialogue`] is sent or [`BlockingAction`] is removed from the dialogue
+    ///
+    /// An action run on [`ActionTiming::Exit`] holds the sentence on screen, one run on [`ActionTiming::Enter`]
+    /// holds its typewriter
+    /// ```
+    /// # use bevy_dialogue_system::prelude::*;
+    /// # use bevy::prelude::*;
+    /// fn pan_camera(In(_dialogue): In<Entity>, world: &mut World) {}
+    ///
+    /// let mut app = App::new();
+    /// app.add_plugins((MinimalPlugins, AssetPlugin::default(), bevy::input::InputPlugin, DialoguePlugin));
+    /// # app.finish();
+    /// # app.cleanup();
+    ///
+    /// let dialogue = app
+    ///     .world
+    ///     .spawn(Dialogue::new().with_paragraphs(vec![Paragraph::new().with_sentences(vec![
+    ///         Sentence::new().with_text("Look over there! ").with_dialogue_action(pan_camera).with_blocking_action(true),
+    ///         Sentence::new().with_text("A dragon!"),
+    ///     ])]))
+    ///     .id();
+    /// let current = |app: &App| {
+    ///     let paragraph = app.world.get::<Dialogue>(dialogue).unwrap().get_current_paragraph().unwrap();
+    ///     paragraph.get_current_sentence().unwrap().text().to_string()
+    /// };
+    /// app.update();
+    ///
+    /// for _ in 0..3 {
+    ///     app.world.send_event(DialogueInput::to(dialogue, DialogueAction::Advance));
+    ///     app.update();
+    /// }
+    /// assert_eq!(current(&app), "Look over there! ");
+    /// assert!(app.world.get::<Dialogue>(dialogue).unwrap().is_waiting());
+    /// assert!(app.world.get::<BlockingAction>(dialogue).is_some());
+    ///
+    /// app.world.send_event(ResumeDialogue(dialogue));
+    /// app.update();
+    /// assert_eq!(current(&app), "A dragon!");
+    /// ```
+    pub fn with_blocking_action(mut self, blocking: bool) -> Self {
+        self.blocking = blocking;
+        self
+    }
+    pub fn set_blocking_action(&mut self, blocking: bool) {
+        self.blocking = blocking;
+    }
+    pub fn has_blocking_action(&self) -> bool {
+        self.blocking
+    }
+    /// The action to run when the sentence is entered or left
+    pub(crate) fn action_on(&self, timing: ActionTiming) -> Option<SentenceAction> {
+        self.action
+            .clone()
+            .filter(|_| self.action_timing == timing && !self.skipped)
+    }
+
+    /// An empty sentence that pauses the dialogue for `seconds`, for pacing a cutscene
+    /// ```
+    /// # use bevy_dialogue_system::prelude::*;
+    /// # use bevy::{prelude::*, time::TimeUpdateStrategy};
+    /// # use std::time::Duration;
+    /// let mut app = App::new();
+    /// app.add_plugins((MinimalPlugins, AssetPlugin::default(), bevy::input::InputPlugin, DialoguePlugin))
+    ///     .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(300)));
+    /// # app.finish();
+    /// # app.cleanup();
+    ///
+    /// let dialogue = app
+    ///     .world
+    ///     .spawn(Dialogue::new().with_paragraphs(vec![Paragraph::new().with_sentences(vec![
+    ///         Sentence::wait_for(1.),
+    ///         Sentence::new().with_text("Boo!"),
+    ///     ])]))
+    ///     .id();
+    /// let current = |app: &App| {
+    ///     let dialogue = app.world.get::<Dialogue>(dialogue).unwrap();
+    ///     dialogue.get_current_paragraph().unwrap().current_sentence_index()
+    /// };
+    /// app.update();
+    ///
+    /// app.world.send_event(DialogueInput::to(dialogue, DialogueAction::Advance));
+    /// app.update();
+    /// assert_eq!(current(&app), 0);
+    /// assert!(app.world.get::<Dialogue>(dialogue).unwrap().is_pausing());
+    ///
+    /// (0..4).for_each(|_| app.update());
+    /// assert_eq!(current(&app), 1);
+    /// ```
+    pub fn wait_for(seconds: f32) -> Self {
+        Self::new().with_wait(DialogueWait::Seconds(seconds))
+    }
+    /// An empty sentence that waits for the player, even in auto mode or while skipping
+    pub fn wait_for_input() -> Self {
+        Self::new().with_wait(DialogueWait::Input)
+    }
+    /// A pause once the sentence has been typed out
+    pub fn with_wait(mut self, wait: DialogueWait) -> Self {
+        self.wait = Some(wait);
+        self
+    }
+    pub fn remove_wait(mut self) -> Self {
+        self.wait = None;
+        self
+    }
+    pub fn set_wait(&mut self, wait: DialogueWait) {
+        self.wait = Some(wait);
+    }
+    pub fn get_wait(&self) -> Option<&DialogueWait> {
+        self.wait.as_ref()
+    }
+
+    /// The name of the paragraph to go to once this sentence has been shown, skipping the rest of the paragraph
+    pub fn with_jump(mut self, target: impl ToString) -> Self {
+        self.jump = Some(target.to_string());
+        self
+    }
+    pub fn remove_jump(mut self) -> Self {
+        self.jump = None;
+        self
+    }
+    pub fn set_jump(&mut self, target: impl ToString) {
+        self.jump = Some(target.to_string());
+    }
+    pub fn get_jump(&self) -> Option<&String> {
+        self.jump.as_ref()
+    }
+
+    /// Only show the sentence if `condition` holds when the dialogue reaches it
+    pub fn with_condition(mut self, condition: DialogueCondition) -> Self {
+        self.condition = Some(condition);
+        self
+    }
+    pub fn remove_condition(mut self) -> Self {
+        self.condition = None;
+        self
+    }
+    pub fn set_condition(&mut self, condition: DialogueCondition) {
+        self.condition = Some(condition);
+    }
+    pub fn get_condition(&self) -> Option<&DialogueCondition> {
+        self.condition.as_ref()
+    }
+    pub fn condition_holds(&self, variables: &DialogueVariables) -> bool {
+        self.condition
+            .as_ref()
+            .is_none_or(|condition| condition.evaluate(variables))
+    }
+
+    /// Changes made to the [`DialogueVariables`] as soon as the sentence is shown
+    pub fn with_variable_changes(mut self, changes: Vec<VariableChange>) -> Self {
+        self.variable_changes = changes;
+        self
+    }
+    pub fn push_variable_change(mut self, change: VariableChange) -> Self {
+        self.variable_changes.push(change);
+        self
+    }
+    pub fn set_variable_changes(&mut self, changes: Vec<VariableChange>) {
+        self.variable_changes = changes;
+    }
+    pub fn add_variable_change(&mut self, change: VariableChange) {
+        self.variable_changes.push(change);
+    }
+    pub fn variable_changes(&self) -> &Vec<VariableChange> {
+        &self.variable_changes
+    }
+    pub fn apply_variable_changes(&self, variables: &mut DialogueVariables) {
+        self.variable_changes
+            .iter()
+            .for_each(|change| variables.apply(change));
+    }
+
+    /// Whether the sentence was passed over because its condition didn't hold
+    pub fn is_skipped(&self) -> bool {
+        self.skipped
+    }
+    pub(crate) fn skip(&mut self) {
+        self.skipped = true;
+    }
+    pub(crate) fn unskip(&mut self) {
+        self.skipped = false;
+    }
 
     pub fn create_typewriter(mut self) -> Self {
         self.typewriter = TypeWriter::new();
@@ -206,17 +779,277 @@ impl Sentence {
         &mut self.typewriter
     }
 
+    /// How many characters the typewriter has revealed, counting grapheme clusters so that accented letters and
+    /// emoji made of several code points show up whole
+    /// ```
+    /// # use bevy_dialogue_system::prelude::*;
+    /// let sentence = Sentence::new()
+    ///     .with_text("ne\u{301}e 🇯🇵")
+    ///     .with_typewriter(TypeWriter::new().with_time(0.6));
+    ///
+    /// assert_eq!(sentence.typewriter_characters(), Some(3));
+    /// assert_eq!(sentence.to_string(), "ne\u{301}e");
+    /// ```
     pub fn typewriter_characters(&self) -> Option<usize> {
         if self.typewriter.active {
-            Some((self.typewriter.time * self.text_section.value.len() as f32) as usize)
+            Some(
+                self.typewriter
+                    .revealed(self.text().graphemes(true).count()),
+            )
         } else {
             None
         }
     }
+    /// The sound the typewriter's voice makes for the characters revealed since this was last called, if any
+    ///
+    /// Only one sound is returned however many characters were revealed, see [`TypeWriterVoice`]
+    pub fn speak(&mut self) -> Option<VoiceBlip> {
+        let revealed = self.typewriter_characters()?;
+        let voice = self.typewriter.voice.as_ref()?;
+        let spoken = self.typewriter.spoken;
+        let mut voiced = self.typewriter.voiced;
+        let mut blip = None;
+
+        self.text_section
+            .value
+            .graphemes(true)
+            .enumerate()
+            .take(revealed)
+            .skip(spoken)
+            .filter(|(_, grapheme)| TypeWriterVoice::is_voiced(grapheme))
+            .for_each(|(index, _)| {
+                if voiced.is_multiple_of(voice.every()) {
+                    blip = voice.blip((&self.text_section.value, index));
+                }
+                voiced += 1;
+            });
+
+        self.typewriter.spoken = spoken.max(revealed);
+        self.typewriter.voiced = voiced;
+        blip
+    }
+    /// The length in bytes of the text the typewriter has revealed, always ending between two characters
+    /// ```
+    /// # use bevy_dialogue_system::prelude::*;
+    /// let mut sentence = Sentence::new()
+    ///     .with_text("こんにちは")
+    ///     .with_typewriter(TypeWriter::new().with_time(0.5));
+    ///
+    /// assert_eq!(sentence.typewriter_bytes(), Some(6));
+    /// assert_eq!(sentence.to_string(), "こん");
+    ///
+    /// sentence.mut_typewriter().finish();
+    /// assert_eq!(sentence.to_string(), "こんにちは");
+    /// ```
+    pub fn typewriter_bytes(&self) -> Option<usize> {
+        let characters = self.typewriter_characters()?;
+
+        Some(
+            self.text()
+                .grapheme_indices(true)
+                .nth(characters)
+                .map_or(self.text().len(), |(index, _)| index),
+        )
+    }
+    /// Moves the typewriter along `amount` seconds, waiting after punctuation and at `{wait}` cues
+    /// ```
+    /// # use bevy_dialogue_system::prelude::*;
+    /// let settings = TypeWriterSettings::characters_per_second(10.);
+    /// let mut sentence = Sentence::new().with_markup("Hi, you{wait=1}!").create_typewriter();
+    ///
+    /// sentence.advance_typewriter(0.35, &settings, &TypeWriterPauses::default());
+    /// assert_eq!(sentence.to_string(), "Hi,");
+    ///
+    /// sentence.advance_typewriter(0.35, &settings, &TypeWriterPauses::default());
+    /// assert_eq!(sentence.to_string(), "Hi, y");
+    ///
+    /// sentence.advance_typewriter(1., &settings, &TypeWriterPauses::default());
+    /// assert_eq!(sentence.to_string(), "Hi, you");
+    ///
+    /// sentence.advance_typewriter(1., &settings, &TypeWriterPauses::default());
+    /// assert_eq!(sentence.to_string(), "Hi, you!");
+    ///
+    /// let mut sentence = Sentence::new()
+    ///     .with_markup("{speed=0.5}I... I don't {speed=4}KNOW what you mean!")
+    ///     .create_typewriter();
+    /// sentence.advance_typewriter(0.4, &settings, &TypeWriterPauses::empty());
+    /// assert_eq!(sentence.to_string(), "I.");
+    ///
+    /// sentence.mut_typewriter().finish();
+    /// assert_eq!(sentence.to_string(), "I... I don't KNOW what you mean!");
+    /// ```
+    pub fn advance_typewriter(
+        &mut self,
+        amount: f32,
+        settings: &TypeWriterSettings,
+        pauses: &TypeWriterPauses,
+    ) {
+        let (schedule, duration) = self.reveal_schedule(settings, pauses);
+
+        self.typewriter.set_schedule(schedule);
+        self.typewriter.advance_seconds(amount, duration);
+        self.tick_reveal(amount);
+    }
+    /// Keeps the reveal effect going for characters still animating in once the typewriter has moved on
+    pub(crate) fn tick_reveal(&mut self, amount: f32) {
+        if self.typewriter.reveal.is_none() {
+            return;
+        }
+        if let Some(revealed) = self.typewriter_characters() {
+            self.typewriter.tick_reveal(amount, revealed);
+        }
+    }
+    /// When each character is revealed as a fraction of the whole sentence, along with how many seconds that takes
+    fn reveal_schedule(
+        &self,
+        settings: &TypeWriterSettings,
+        pauses: &TypeWriterPauses,
+    ) -> (Vec<f32>, f32) {
+        let text = self.text();
+        let character_duration = self.typewriter.character_duration(text, settings);
+        let graphemes = text.grapheme_indices(true).collect::<Vec<_>>();
+        let pause = |grapheme: &str| grapheme.chars().next().and_then(|first| pauses.get(first));
+
+        let mut cues = self.cues.iter().peekable();
+        let mut time = 0.;
+        let mut speed = 1.;
+        let mut reveals = Vec::with_capacity(graphemes.len());
+
+        graphemes
+            .iter()
+            .enumerate()
+            .for_each(|(index, (position, grapheme))| {
+                while let Some((_, cue)) =
+                    cues.next_if(|(cue_position, _)| cue_position <= position)
+                {
+                    match cue {
+                        TypeWriterCue::Wait(seconds) => time += seconds,
+                        TypeWriterCue::Speed(multiplier) => speed = *multiplier,
+                    }
+                }
+
+                time += character_duration / speed;
+                reveals.push(time);
+
+                let next = graphemes.get(index + 1).map(|(_, next)| *next);
+                if next.is_some_and(|next| pause(next).is_none()) {
+                    time += pause(grapheme).unwrap_or_default();
+                }
+            });
+        cues.for_each(|(_, cue)| {
+            if let TypeWriterCue::Wait(seconds) = cue {
+                time += seconds;
+            }
+        });
+
+        let schedule = reveals.into_iter().map(|reveal| reveal / time).collect();
+        (schedule, time)
+    }
     pub fn is_typewriter_finished(&self) -> bool {
         !self.typewriter.active || self.typewriter.time == 1.
     }
 
+    /// The text shown so far, split wherever its markup changes the style
+    /// and around each character still animating in with [`TypeWriterReveal`]
+    pub fn as_text_sections(&self) -> Vec<TextSection> {
+        self.pieces()
+            .into_iter()
+            .map(|piece| piece.section)
+            .collect()
+    }
+    /// How far each of [`Sentence::as_text_sections`] is moved by the reveal effect, in character heights
+    pub fn reveal_offsets(&self) -> Vec<Vec2> {
+        self.pieces()
+            .into_iter()
+            .map(|piece| piece.offset)
+            .collect()
+    }
+    /// [`Sentence::as_text_sections`] with its [`TextEffect`]s played `time` seconds in,
+    /// along with how far each is moved in character heights
+    pub fn animate(&self, time: f32, settings: &TextEffectSettings) -> Vec<(TextSection, Vec2)> {
+        self.pieces()
+            .into_iter()
+            .map(|mut piece| {
+                if settings.reduced_motion {
+                    piece.offset = Vec2::ZERO;
+                }
+                piece.effects.iter().for_each(|effect| {
+                    effect.apply(
+                        piece.character,
+                        time,
+                        settings,
+                        &mut piece.section.style.color,
+                        &mut piece.offset,
+                    )
+                });
+                (piece.section, piece.offset)
+            })
+            .collect()
+    }
+    /// The shown text split wherever its style changes, with characters that animate getting a section of their own
+    fn pieces(&self) -> Vec<Piece> {
+        let text = self.text();
+        let shown = self.typewriter_bytes().unwrap_or(text.len());
+        let reveal = self.typewriter.reveal.unwrap_or_default();
+        let mut pieces: Vec<Piece> = Vec::new();
+
+        text.grapheme_indices(true)
+            .enumerate()
+            .take_while(|(_, (start, _))| *start < shown)
+            .for_each(|(character, (start, grapheme))| {
+                let span = self
+                    .spans
+                    .iter()
+                    .position(|span| span.start <= start && start < span.end);
+                let progress = self.typewriter.reveal_progress(character);
+                let effects = self
+                    .effects
+                    .iter()
+                    .chain(span.iter().flat_map(|span| &self.spans[*span].effects))
+                    .copied()
+                    .collect::<Vec<_>>();
+                let settled = progress >= 1. && effects.is_empty();
+
+                match pieces.last_mut() {
+                    Some(last) if settled && last.settled && last.span == span => {
+                        last.section.value.push_str(grapheme)
+                    }
+                    _ => {
+                        let mut style = self.span_style(span.map(|span| &self.spans[span]));
+                        style.color = style.color.with_a(style.color.a() * reveal.alpha(progress));
+
+                        pieces.push(Piece {
+                            section: TextSection::new(grapheme, style),
+                            offset: reveal.offset(progress),
+                            effects,
+                            character,
+                            span,
+                            settled,
+                        });
+                    }
+                }
+            });
+
+        pieces
+    }
+    fn span_style(&self, span: Option<&MarkupSpan>) -> TextStyle {
+        let mut style = self.text_section.style.clone();
+
+        if let Some(span) = span {
+            if let Some(color) = span.color {
+                style.color = color;
+            }
+            if let Some(size) = span.font_size {
+                style.font_size = size;
+            }
+            if let Some(font) = self.bold_font.as_ref().filter(|_| span.bold) {
+                style.font = font.clone();
+            }
+        }
+
+        style
+    }
+
     pub fn as_text_section(&self) -> TextSection {
         TextSection {
             value: self.to_string(),
@@ -227,3 +1060,14 @@ impl Sentence {
         }
     }
 }
+
+/// Part of a sentence's shown text, along with what it needs to animate
+struct Piece {
+    section: TextSection,
+    offset: Vec2,
+    effects: Vec<TextEffect>,
+    /// The index of its first character in the sentence
+    character: usize,
+    span: Option<usize>,
+    settled: bool,
+}
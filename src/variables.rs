@@ -0,0 +1,196 @@
+use crate::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+pub enum DialogueValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+}
+impl DialogueValue {
+    /// Whether the value counts as true when used as a condition on its own
+    /// ```
+    /// # use bevy_dialogue_system::prelude::*;
+    /// assert!(DialogueValue::from(true).is_truthy());
+    /// assert!(DialogueValue::from(3).is_truthy());
+    /// assert!(!DialogueValue::from(0.).is_truthy());
+    /// assert!(!DialogueValue::from("").is_truthy());
+    /// ```
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            Self::Bool(value) => *value,
+            Self::Int(value) => *value != 0,
+            Self::Float(value) => *value != 0.,
+            Self::String(value) => !value.is_empty(),
+        }
+    }
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::Int(value) => Some(*value as f64),
+            Self::Float(value) => Some(*value),
+            _ => None,
+        }
+    }
+    /// Compares two values, ints and floats can be compared with each other but otherwise the kinds must match
+    /// ```
+    /// # use bevy_dialogue_system::prelude::*;
+    /// # use std::cmp::Ordering;
+    /// assert_eq!(DialogueValue::from(2).compare(&DialogueValue::from(2.5)), Some(Ordering::Less));
+    /// assert_eq!(DialogueValue::from("a").compare(&DialogueValue::from(1)), None);
+    /// ```
+    pub fn compare(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        match (self, other) {
+            (Self::Bool(left), Self::Bool(right)) => Some(left.cmp(right)),
+            (Self::Int(left), Self::Int(right)) => Some(left.cmp(right)),
+            (Self::String(left), Self::String(right)) => Some(left.cmp(right)),
+            (left, right) => left.as_f64()?.partial_cmp(&right.as_f64()?),
+        }
+    }
+}
+impl Display for DialogueValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Bool(value) => write!(f, "{}", value),
+            Self::Int(value) => write!(f, "{}", value),
+            Self::Float(value) => write!(f, "{}", value),
+            Self::String(value) => write!(f, "{}", value),
+        }
+    }
+}
+impl From<bool> for DialogueValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+impl From<i32> for DialogueValue {
+    fn from(value: i32) -> Self {
+        Self::Int(value.into())
+    }
+}
+impl From<i64> for DialogueValue {
+    fn from(value: i64) -> Self {
+        Self::Int(value)
+    }
+}
+impl From<f32> for DialogueValue {
+    fn from(value: f32) -> Self {
+        Self::Float(value.into())
+    }
+}
+impl From<f64> for DialogueValue {
+    fn from(value: f64) -> Self {
+        Self::Float(value)
+    }
+}
+impl From<&str> for DialogueValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+}
+impl From<String> for DialogueValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+/// A change made to [`DialogueVariables`] as the dialogue moves along
+#[derive(Debug, Clone, PartialEq)]
+pub enum VariableChange {
+    Set(String, DialogueValue),
+    /// Adds to a number or appends to a string, a missing variable is treated as being set instead
+    Add(String, DialogueValue),
+}
+impl VariableChange {
+    pub fn set(name: impl ToString, value: impl Into<DialogueValue>) -> Self {
+        Self::Set(name.to_string(), value.into())
+    }
+    pub fn add(name: impl ToString, value: impl Into<DialogueValue>) -> Self {
+        Self::Add(name.to_string(), value.into())
+    }
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Set(name, _) | Self::Add(name, _) => name,
+        }
+    }
+}
+
+#[derive(Resource, Default, Debug, Clone)]
+pub struct DialogueVariables(HashMap<String, DialogueValue>);
+
+impl DialogueVariables {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, name: impl ToString, value: impl Into<DialogueValue>) -> Self {
+        self.set(name, value);
+        self
+    }
+    pub fn set(&mut self, name: impl ToString, value: impl Into<DialogueValue>) {
+        self.0.insert(name.to_string(), value.into());
+    }
+    pub fn get(&self, name: &str) -> Option<&DialogueValue> {
+        self.0.get(name)
+    }
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut DialogueValue> {
+        self.0.get_mut(name)
+    }
+    pub fn remove(&mut self, name: &str) -> Option<DialogueValue> {
+        self.0.remove(name)
+    }
+    pub fn contains(&self, name: &str) -> bool {
+        self.0.contains_key(name)
+    }
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &DialogueValue)> {
+        self.0.iter()
+    }
+
+    /// ```
+    /// # use bevy_dialogue_system::prelude::*;
+    /// let mut variables = DialogueVariables::new().with("gold", 5);
+    ///
+    /// variables.apply(&VariableChange::add("gold", 10));
+    /// variables.apply(&VariableChange::add("name", "Ada"));
+    /// variables.apply(&VariableChange::set("has_key", true));
+    ///
+    /// assert_eq!(variables.get("gold"), Some(&DialogueValue::Int(15)));
+    /// assert_eq!(variables.get("name"), Some(&DialogueValue::from("Ada")));
+    /// assert_eq!(variables.get("has_key"), Some(&DialogueValue::Bool(true)));
+    ///
+    /// variables.apply(&VariableChange::add("gold", i64::MAX));
+    /// assert_eq!(variables.get("gold"), Some(&DialogueValue::Int(15)));
+    /// ```
+    pub fn apply(&mut self, change: &VariableChange) {
+        match change {
+            VariableChange::Set(name, value) => self.set(name, value.clone()),
+            VariableChange::Add(name, value) => {
+                let sum = match (self.get(name), value) {
+                    (None, value) => value.clone(),
+                    (Some(DialogueValue::Int(left)), DialogueValue::Int(right)) => {
+                        match left.checked_add(*right) {
+                            Some(sum) => DialogueValue::Int(sum),
+                            None => {
+                                warn!(
+                                    "Adding {right} to {name:?}, which is {left}, would overflow"
+                                );
+                                return;
+                            }
+                        }
+                    }
+                    (Some(DialogueValue::String(left)), right) => {
+                        DialogueValue::String(format!("{}{}", left, right))
+                    }
+                    (Some(left), right) => match (left.as_f64(), right.as_f64()) {
+                        (Some(left), Some(right)) => DialogueValue::Float(left + right),
+                        _ => {
+                            warn!("Could not add {right:?} to {name:?}, which is {left:?}");
+                            return;
+                        }
+                    },
+                };
+                self.set(name, sum);
+            }
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use crate::prelude::*;
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+/// Sounds played as a typewriter reveals characters, giving whoever is speaking a voice
+///
+/// Whitespace and punctuation are silent. Which sound plays and how far its pitch is varied are picked from the
+/// character being revealed, so a line sounds the same every time it is shown
+/// ```
+/// # use bevy_dialogue_system::prelude::*;
+/// # use bevy::prelude::*;
+/// let mut sentence = Sentence::new().with_text("Hi, you!").with_typewriter(
+///     TypeWriter::new().with_voice(TypeWriterVoice::new(Handle::default()).with_every(2)),
+/// );
+///
+/// sentence.mut_typewriter().set_time(0.25);
+/// assert!(sentence.speak().is_some());
+/// assert!(sentence.speak().is_none());
+///
+/// sentence.mut_typewriter().set_time(0.5);
+/// assert!(sentence.speak().is_none());
+///
+/// sentence.mut_typewriter().set_time(0.625);
+/// assert!(sentence.speak().is_some());
+/// ```
+#[derive(Debug, Clone)]
+pub struct TypeWriterVoice {
+    sounds: Vec<Handle<AudioSource>>,
+    every: usize,
+    pitch: f32,
+    pitch_variance: f32,
+    volume: f32,
+}
+impl Default for TypeWriterVoice {
+    fn default() -> Self {
+        Self {
+            sounds: Vec::new(),
+            every: 2,
+            pitch: 1.,
+            pitch_variance: 0.,
+            volume: 1.,
+        }
+    }
+}
+impl TypeWriterVoice {
+    pub fn new(sound: Handle<AudioSource>) -> Self {
+        Self {
+            sounds: vec![sound],
+            ..Default::default()
+        }
+    }
+
+    /// Several sounds to pick between for variety
+    pub fn with_sounds(mut self, sounds: Vec<Handle<AudioSource>>) -> Self {
+        self.sounds = sounds;
+        self
+    }
+    pub fn push_sound(mut self, sound: Handle<AudioSource>) -> Self {
+        self.sounds.push(sound);
+        self
+    }
+    pub fn set_sounds(&mut self, sounds: Vec<Handle<AudioSource>>) {
+        self.sounds = sounds;
+    }
+    pub fn add_sound(&mut self, sound: Handle<AudioSource>) {
+        self.sounds.push(sound);
+    }
+    pub fn sounds(&self) -> &Vec<Handle<AudioSource>> {
+        &self.sounds
+    }
+
+    /// Plays a sound for every this many characters revealed, not counting whitespace and punctuation
+    pub fn with_every(mut self, characters: usize) -> Self {
+        self.set_every(characters);
+        self
+    }
+    pub fn set_every(&mut self, characters: usize) {
+        self.every = characters.max(1);
+    }
+    pub fn every(&self) -> usize {
+        self.every
+    }
+
+    /// How fast the sounds are played, higher being higher pitched
+    pub fn with_pitch(mut self, pitch: f32) -> Self {
+        self.pitch = pitch;
+        self
+    }
+    pub fn set_pitch(&mut self, pitch: f32) {
+        self.pitch = pitch;
+    }
+    pub fn pitch(&self) -> f32 {
+        self.pitch
+    }
+
+    /// How far above or below its pitch each sound can be played
+    pub fn with_pitch_variance(mut self, variance: f32) -> Self {
+        self.pitch_variance = variance;
+        self
+    }
+    pub fn set_pitch_variance(&mut self, variance: f32) {
+        self.pitch_variance = variance;
+    }
+    pub fn pitch_variance(&self) -> f32 {
+        self.pitch_variance
+    }
+
+    pub fn with_volume(mut self, volume: f32) -> Self {
+        self.volume = volume.max(0.);
+        self
+    }
+    pub fn set_volume(&mut self, volume: f32) {
+        self.volume = volume.max(0.);
+    }
+    pub fn volume(&self) -> f32 {
+        self.volume
+    }
+
+    /// Whether revealing the character makes a sound
+    pub fn is_voiced(grapheme: &str) -> bool {
+        grapheme.chars().any(char::is_alphanumeric)
+    }
+
+    pub(crate) fn blip(&self, seed: impl Hash) -> Option<VoiceBlip> {
+        let mut hasher = DefaultHasher::new();
+        seed.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        let sound = self.sounds.get(hash as usize % self.sounds.len().max(1))?;
+        // Between -1 and 1
+        let offset = (hash >> 32) as f32 / u32::MAX as f32 * 2. - 1.;
+
+        Some(VoiceBlip {
+            sound: sound.clone(),
+            pitch: (self.pitch + offset * self.pitch_variance).max(0.01),
+            volume: self.volume,
+        })
+    }
+}
+
+/// A sound to play for a character revealed by the typewriter
+#[derive(Debug, Clone, PartialEq)]
+pub struct VoiceBlip {
+    pub sound: Handle<AudioSource>,
+    pub pitch: f32,
+    pub volume: f32,
+}
@@ -0,0 +1,29 @@
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A pause a sentence holds once it has been typed out, set with [`Sentence::with_wait`] or used on its own
+/// with [`Sentence::wait_for`]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum DialogueWait {
+    /// Ignores the player for this many seconds, then moves on by itself
+    Seconds(f32),
+    /// Only moves on once the player asks to, even in auto mode or while skipping
+    Input,
+}
+impl DialogueWait {
+    /// How long the wait lasts, `None` when it waits for the player
+    pub fn seconds(&self) -> Option<f32> {
+        match self {
+            Self::Seconds(seconds) => Some(*seconds),
+            Self::Input => None,
+        }
+    }
+}
+impl Display for DialogueWait {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Seconds(seconds) => write!(f, "{} seconds", seconds),
+            Self::Input => write!(f, "until the player moves on"),
+        }
+    }
+}
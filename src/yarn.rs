@@ -0,0 +1,779 @@
+use crate::prelude::*;
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    utils::BoxedFuture,
+};
+
+/// Converts a [Yarn Spinner](https://yarnspinner.dev) script into paragraphs
+///
+/// Every node starts with an empty paragraph named after its title, which `<<jump>>` goes to, and the dialogue
+/// stops at the end of each node. The node titled `Start` is put first, otherwise nodes keep their order in the file.
+///
+/// Supported are lines, with or without a `Name:` prefix that becomes the [`Sentence::speaker`], `->` options, `<<if>>`/`<<elseif>>`/`<<else>>`/`<<endif>>`,
+/// `<<set>>` with plain values or `$x + n`, `<<jump>>`, `<<stop>>`, `<<wait>>`, conditions at the end of lines and options and
+/// `#line:` hashtags, which become the line's [`Sentence::line_id`],
+/// as well as the commands of [`DialogueCommands`] when using [`parse_yarn_with`]. Anything else is reported as a [`YarnDiagnostic`] rather than being left out.
+/// ```
+/// # use bevy_dialogue_system::prelude::*;
+/// let paragraphs = parse_yarn(
+///     r#"
+/// title: Start
+/// ---
+/// Guard: Halt! Who goes there? #line:guard_halt
+/// -> A friend
+///     <<set $friendly to true>>
+/// -> None of your business
+/// <<if $friendly>>
+///     Guard: Come on in then.
+/// <<else>>
+///     <<jump Rude>>
+/// <<endif>>
+/// ===
+/// title: Rude
+/// ---
+/// Guard: Be on your way.
+/// ===
+/// "#,
+///     "guard.yarn",
+/// )
+/// .unwrap();
+///
+/// let mut dialogue = Dialogue::new().with_paragraphs(paragraphs);
+/// let mut variables = DialogueVariables::new();
+///
+/// dialogue.start_paragraph(&mut variables);
+/// let paragraph = dialogue.get_current_paragraph_mut().unwrap();
+/// assert_eq!(paragraph.sentences()[0].text(), "Halt! Who goes there?");
+/// assert_eq!(paragraph.sentences()[0].speaker(), Some("Guard"));
+/// assert_eq!(paragraph.sentences()[0].line_id(), Some("guard_halt"));
+///
+/// paragraph.update_typewriter(10., &TypeWriterSettings::default());
+/// dialogue.select_choice(1);
+/// dialogue.start_paragraph(&mut variables);
+/// assert_eq!(dialogue.get_current_paragraph().unwrap().sentences()[0].text(), "Be on your way.");
+///
+/// let paragraphs = parse_yarn("title: Start\n---\nSee https://example.com // the wiki\nNote\\: it's 10:30.\n===\n", "url.yarn").unwrap();
+/// assert_eq!(paragraphs[1].sentences()[0].text(), "See https://example.com");
+/// assert_eq!(paragraphs[2].sentences()[0].text(), "Note: it's 10:30.");
+/// assert_eq!(paragraphs[2].sentences()[0].speaker(), None);
+///
+/// let errors = parse_yarn("title: Start\n---\nYou have {$gold} gold.\n<<fade_out 2>>\n===\n", "gold.yarn").unwrap_err();
+/// assert_eq!(errors.diagnostics()[0].line, 3);
+/// assert_eq!(errors.diagnostics()[1].to_string(), "gold.yarn:4: unsupported command <<fade_out 2>>");
+/// ```
+pub fn parse_yarn(source: &str, file: &str) -> Result<Vec<Paragraph>, YarnError> {
+    parse_yarn_with(source, file, &DialogueCommands::default())
+}
+
+/// Converts a yarn script like [`parse_yarn`], turning lines such as `<<give_item sword 1>>` into calls to `commands`
+/// ```
+/// # use bevy_dialogue_system::prelude::*;
+/// # use bevy::prelude::*;
+/// fn give_item(In(input): In<CommandInput>) {}
+///
+/// let commands = DialogueCommands::new().with("give_item", [CommandArgument::String], give_item);
+/// let script = "title: Start\n---\n<<give_item sword>>\nHere you go.\n===\n";
+///
+/// let paragraphs = parse_yarn_with(script, "gift.yarn", &commands).unwrap();
+/// assert_eq!(paragraphs[1].commands()[0].to_string(), "<<give_item sword>>");
+///
+/// let errors = parse_yarn_with("title: Start\n---\n<<give_item>>\n===\n", "gift.yarn", &commands).unwrap_err();
+/// assert_eq!(errors.diagnostics()[0].message, "<<give_item>> takes 1 argument, found 0");
+/// ```
+pub fn parse_yarn_with(
+    source: &str,
+    file: &str,
+    commands: &DialogueCommands,
+) -> Result<Vec<Paragraph>, YarnError> {
+    let mut diagnostics = Vec::new();
+    let mut nodes = split_nodes(source, file, &mut diagnostics);
+
+    if let Some(start) = nodes.iter().position(|node| node.title == "Start") {
+        let start = nodes.remove(start);
+        nodes.insert(0, start);
+    }
+
+    let mut compiler = Compiler::default();
+    nodes.iter().for_each(|node| {
+        let mut parser = Parser {
+            lines: &node.body,
+            position: 0,
+            file,
+            commands,
+            diagnostics: &mut diagnostics,
+        };
+        let statements = parser.parse_node();
+
+        compiler.node = node.title.clone();
+        compiler.push(Paragraph::new().with_name(&node.title));
+        compiler.compile(statements);
+        compiler.push(Paragraph::new().with_stop(true));
+    });
+
+    compiler
+        .jumps
+        .iter()
+        .filter(|(target, _)| !nodes.iter().any(|node| node.title == *target))
+        .for_each(|(target, line)| {
+            diagnostics.push(YarnDiagnostic::new(
+                file,
+                *line,
+                format!("jump to missing node {:?}", target),
+            ))
+        });
+
+    if diagnostics.is_empty() {
+        Ok(compiler.paragraphs)
+    } else {
+        Err(YarnError { diagnostics })
+    }
+}
+
+/// A problem found in a yarn script
+#[derive(Debug, Clone, PartialEq)]
+pub struct YarnDiagnostic {
+    pub file: String,
+    pub line: usize,
+    pub message: String,
+}
+impl YarnDiagnostic {
+    fn new(file: &str, line: usize, message: impl ToString) -> Self {
+        Self {
+            file: file.to_string(),
+            line,
+            message: message.to_string(),
+        }
+    }
+}
+impl Display for YarnDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}: {}", self.file, self.line, self.message)
+    }
+}
+
+/// Every problem found in a yarn script
+#[derive(Debug, Clone, PartialEq)]
+pub struct YarnError {
+    diagnostics: Vec<YarnDiagnostic>,
+}
+impl YarnError {
+    pub fn diagnostics(&self) -> &[YarnDiagnostic] {
+        &self.diagnostics
+    }
+}
+impl Display for YarnError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.diagnostics
+            .iter()
+            .enumerate()
+            .try_for_each(|(index, diagnostic)| {
+                if index > 0 {
+                    writeln!(f)?;
+                }
+                write!(f, "{}", diagnostic)
+            })
+    }
+}
+impl std::error::Error for YarnError {}
+
+/// Loads `.yarn` files as a [`DialogueAsset`], with the commands of the [`DialogueCommands`] resource, including
+/// commands added to it once the app is running
+pub struct YarnAssetLoader {
+    commands: DialogueCommands,
+}
+impl FromWorld for YarnAssetLoader {
+    fn from_world(world: &mut World) -> Self {
+        Self {
+            commands: world
+                .get_resource::<DialogueCommands>()
+                .cloned()
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl AssetLoader for YarnAssetLoader {
+    type Asset = DialogueAsset;
+    type Settings = ();
+    type Error = DialogueAssetError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a Self::Settings,
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
+        Box::pin(async move {
+            let mut source = String::new();
+            reader.read_to_string(&mut source).await?;
+
+            let paragraphs = parse_yarn_with(
+                &source,
+                &load_context.path().display().to_string(),
+                &self.commands,
+            )
+            .map_err(DialogueAssetError::Yarn)?;
+
+            Ok(DialogueAsset::new(paragraphs))
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["yarn"]
+    }
+}
+
+struct Line {
+    number: usize,
+    indent: usize,
+    text: String,
+    /// From a `#line:` hashtag
+    id: Option<String>,
+}
+
+struct Node {
+    title: String,
+    body: Vec<Line>,
+}
+
+fn split_nodes(source: &str, file: &str, diagnostics: &mut Vec<YarnDiagnostic>) -> Vec<Node> {
+    let mut nodes = Vec::new();
+    let mut title = None;
+    let mut header_line = 1;
+    let mut body: Option<Vec<Line>> = None;
+
+    source.lines().enumerate().for_each(|(index, raw)| {
+        let number = index + 1;
+        let (text, id) = clean_line(raw);
+        let trimmed = text.trim();
+
+        match body.as_mut() {
+            Some(lines) if trimmed == "===" => {
+                match title.take() {
+                    Some(title) => nodes.push(Node {
+                        title,
+                        body: std::mem::take(lines),
+                    }),
+                    None => diagnostics.push(YarnDiagnostic::new(
+                        file,
+                        header_line,
+                        "node has no title",
+                    )),
+                }
+                body = None;
+            }
+            Some(lines) => {
+                if !trimmed.is_empty() {
+                    lines.push(Line {
+                        number,
+                        indent: text
+                            .chars()
+                            .take_while(|character| character.is_whitespace())
+                            .map(|character| if character == '\t' { 4 } else { 1 })
+                            .sum(),
+                        text: trimmed.to_string(),
+                        id: id.map(str::to_string),
+                    });
+                }
+            }
+            None if trimmed == "---" => body = Some(Vec::new()),
+            None if trimmed.is_empty() => {}
+            None => match trimmed.split_once(':') {
+                Some((key, value)) => {
+                    if title.is_none() {
+                        header_line = number;
+                    }
+                    if key.trim() == "title" {
+                        title = Some(value.trim().to_string());
+                    }
+                }
+                None => diagnostics.push(YarnDiagnostic::new(
+                    file,
+                    number,
+                    format!(
+                        "expected a header such as `title: Start`, found {:?}",
+                        trimmed
+                    ),
+                )),
+            },
+        }
+    });
+
+    if body.is_some() {
+        diagnostics.push(YarnDiagnostic::new(
+            file,
+            header_line,
+            "node is missing its closing ===",
+        ));
+    }
+
+    nodes
+}
+
+/// Strips comments and trailing hashtags, returning the id of a `#line:` hashtag
+fn clean_line(line: &str) -> (&str, Option<&str>) {
+    let mut line = line[..comment_start(line)].trim_end();
+    let mut id = None;
+
+    loop {
+        let (rest, tag) = match line.rfind(char::is_whitespace) {
+            Some(index) if line[index..].trim_start().starts_with('#') => {
+                (line[..index].trim_end(), line[index..].trim_start())
+            }
+            None if line.trim_start().starts_with('#') => ("", line.trim_start()),
+            _ => return (line, id),
+        };
+        id = tag.strip_prefix("#line:").or(id);
+        line = rest;
+    }
+}
+
+/// Where a `//` comment starts, which it only does at the start of the line or after whitespace outside of markup,
+/// so that `https://` and the like are kept
+fn comment_start(line: &str) -> usize {
+    let mut depth = 0usize;
+    let mut previous = None;
+
+    for (index, character) in line.char_indices() {
+        match character {
+            '[' => depth += 1,
+            ']' => depth = depth.saturating_sub(1),
+            '/' if depth == 0
+                && line[index..].starts_with("//")
+                && previous.is_none_or(char::is_whitespace) =>
+            {
+                return index
+            }
+            _ => {}
+        }
+        previous = Some(character);
+    }
+    line.len()
+}
+
+/// Splits the `Name:` a line starts with from its text, only a colon followed by whitespace counting and one
+/// written as `\:` never counting
+fn split_speaker(text: &str) -> (Option<String>, String) {
+    let colon = text
+        .char_indices()
+        .find(|&(index, character)| {
+            character == ':'
+                && !text[..index].ends_with('\\')
+                && text[index + 1..].starts_with(char::is_whitespace)
+        })
+        .map(|(index, _)| index);
+    let unescape = |text: &str| text.replace("\\:", ":");
+
+    match colon {
+        Some(index)
+            if !text[..index].trim().is_empty() && !text[..index].contains(['[', '{', '<']) =>
+        {
+            (
+                Some(unescape(text[..index].trim())),
+                unescape(text[index + 1..].trim()),
+            )
+        }
+        _ => (None, unescape(text)),
+    }
+}
+
+enum Statement {
+    Line {
+        speaker: Option<String>,
+        text: String,
+        id: Option<String>,
+        condition: Option<DialogueCondition>,
+    },
+    Options(Vec<YarnOption>),
+    If(Vec<(Option<DialogueCondition>, Vec<Statement>)>),
+    Jump(String, usize),
+    Set(VariableChange),
+    Command(DialogueCommand),
+    Wait(f32),
+    Stop,
+}
+
+struct YarnOption {
+    text: String,
+    condition: Option<DialogueCondition>,
+    body: Vec<Statement>,
+}
+
+struct Parser<'a> {
+    lines: &'a [Line],
+    position: usize,
+    file: &'a str,
+    commands: &'a DialogueCommands,
+    diagnostics: &'a mut Vec<YarnDiagnostic>,
+}
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&'a Line> {
+        self.lines.get(self.position)
+    }
+    fn error(&mut self, line: usize, message: impl ToString) {
+        self.diagnostics
+            .push(YarnDiagnostic::new(self.file, line, message));
+    }
+
+    fn parse_node(&mut self) -> Vec<Statement> {
+        let mut statements = self.parse_block(None);
+
+        while let Some(line) = self.peek() {
+            self.error(
+                line.number,
+                format!("{} without a matching <<if>>", line.text),
+            );
+            self.position += 1;
+            statements.append(&mut self.parse_block(None));
+        }
+
+        statements
+    }
+
+    /// Parses statements until one is indented at or below `limit`, or an `<<elseif>>`, `<<else>>` or `<<endif>>`
+    fn parse_block(&mut self, limit: Option<usize>) -> Vec<Statement> {
+        let mut statements = Vec::new();
+
+        while let Some(line) = self.peek() {
+            if limit.is_some_and(|limit| line.indent <= limit) {
+                break;
+            }
+
+            if let Some(command) = as_command(&line.text) {
+                let (keyword, rest) = command.split_once(' ').unwrap_or((command, ""));
+                let rest = rest.trim();
+
+                match keyword {
+                    "elseif" | "else" | "endif" => break,
+                    "if" => {
+                        self.position += 1;
+                        let condition = self.parse_condition(rest, line.number);
+                        statements.push(self.parse_if(condition, line, limit));
+                        continue;
+                    }
+                    "jump" if !rest.is_empty() && !rest.contains('{') => {
+                        statements.push(Statement::Jump(rest.to_string(), line.number))
+                    }
+                    "set" => {
+                        if let Some(change) = self.parse_set(rest, line.number) {
+                            statements.push(Statement::Set(change));
+                        }
+                    }
+                    "stop" => statements.push(Statement::Stop),
+                    "wait" => match rest.parse() {
+                        Ok(seconds) => statements.push(Statement::Wait(seconds)),
+                        Err(_) => self.error(line.number, "expected <<wait seconds>>"),
+                    },
+                    _ if self.commands.contains(keyword) => match self.commands.parse(command) {
+                        Ok(command) => statements.push(Statement::Command(command)),
+                        Err(error) => self.error(line.number, error),
+                    },
+                    _ => self.error(line.number, format!("unsupported command <<{}>>", command)),
+                }
+                self.position += 1;
+            } else if line.text.starts_with("->") {
+                statements.push(self.parse_options(line.indent));
+            } else if line.text.starts_with("=>") {
+                self.error(line.number, "line groups aren't supported");
+                self.position += 1;
+            } else {
+                let (text, condition) = self.split_inline_condition(&line.text, line.number);
+                if text.contains('{') {
+                    self.error(line.number, "inline expressions aren't supported");
+                }
+                let (speaker, text) = split_speaker(&text);
+                statements.push(Statement::Line {
+                    speaker,
+                    text,
+                    id: line.id.clone(),
+                    condition,
+                });
+                self.position += 1;
+            }
+        }
+
+        statements
+    }
+
+    fn parse_if(
+        &mut self,
+        condition: Option<DialogueCondition>,
+        start: &Line,
+        limit: Option<usize>,
+    ) -> Statement {
+        let mut branches = Vec::new();
+        let mut condition = condition;
+
+        loop {
+            let body = self.parse_block(limit);
+            branches.push((condition.take(), body));
+
+            let Some(line) = self.peek() else {
+                self.error(start.number, "<<if>> is missing its <<endif>>");
+                break;
+            };
+            let command = as_command(&line.text).unwrap_or_default();
+            let (keyword, rest) = command.split_once(' ').unwrap_or((command, ""));
+
+            match keyword {
+                "elseif" => {
+                    self.position += 1;
+                    condition = self.parse_condition(rest.trim(), line.number);
+                }
+                "else" => self.position += 1,
+                "endif" => {
+                    self.position += 1;
+                    break;
+                }
+                _ => {
+                    self.error(start.number, "<<if>> is missing its <<endif>>");
+                    break;
+                }
+            }
+        }
+
+        Statement::If(branches)
+    }
+
+    fn parse_options(&mut self, indent: usize) -> Statement {
+        let mut options = Vec::new();
+
+        while let Some(line) = self
+            .peek()
+            .filter(|line| line.indent == indent && line.text.starts_with("->"))
+        {
+            self.position += 1;
+
+            let (text, condition) = self.split_inline_condition(&line.text[2..], line.number);
+            if text.contains('{') {
+                self.error(line.number, "inline expressions aren't supported");
+            }
+            options.push(YarnOption {
+                text,
+                condition,
+                body: self.parse_block(Some(indent)),
+            });
+        }
+
+        Statement::Options(options)
+    }
+
+    fn parse_condition(&mut self, condition: &str, line: usize) -> Option<DialogueCondition> {
+        DialogueCondition::parse(condition)
+            .map_err(|error| self.error(line, format!("invalid condition: {}", error)))
+            .ok()
+    }
+
+    fn parse_set(&mut self, set: &str, line: usize) -> Option<VariableChange> {
+        let Some((name, value)) = set.split_once(" to ").or_else(|| set.split_once('=')) else {
+            self.error(line, "expected <<set $name to value>>");
+            return None;
+        };
+        let name = name.trim().trim_start_matches('$');
+        let value = value.trim();
+
+        // Only adding to or taking away from the variable being set is supported
+        let increment = [('+', 1), ('-', -1)]
+            .into_iter()
+            .find_map(|(operator, sign)| {
+                let (variable, amount) = value.split_once(operator)?;
+                (variable.trim().trim_start_matches('$') == name).then_some((amount.trim(), sign))
+            });
+
+        match increment {
+            Some((amount, sign)) => match DialogueCondition::parse(amount) {
+                Ok(DialogueCondition::Literal(DialogueValue::Int(amount))) => {
+                    Some(VariableChange::add(name, amount * sign))
+                }
+                Ok(DialogueCondition::Literal(DialogueValue::Float(amount))) => {
+                    Some(VariableChange::add(name, amount * sign as f64))
+                }
+                Ok(DialogueCondition::Literal(value)) if sign > 0 => {
+                    Some(VariableChange::Add(name.to_string(), value))
+                }
+                _ => {
+                    self.error(line, format!("unsupported expression {:?}", value));
+                    None
+                }
+            },
+            None => match DialogueCondition::parse(value) {
+                Ok(DialogueCondition::Literal(value)) => {
+                    Some(VariableChange::Set(name.to_string(), value))
+                }
+                _ => {
+                    self.error(line, format!("unsupported expression {:?}", value));
+                    None
+                }
+            },
+        }
+    }
+
+    /// Splits off an `<<if condition>>` at the end of a line or option
+    fn split_inline_condition(
+        &mut self,
+        text: &str,
+        line: usize,
+    ) -> (String, Option<DialogueCondition>) {
+        let text = text.trim();
+        let Some(start) = text.rfind("<<").filter(|_| text.ends_with(">>")) else {
+            return (text.to_string(), None);
+        };
+
+        let command = &text[start + 2..text.len() - 2];
+        let condition = match command.trim().strip_prefix("if ") {
+            Some(condition) => self.parse_condition(condition, line),
+            None => {
+                self.error(line, format!("unsupported command <<{}>>", command.trim()));
+                None
+            }
+        };
+
+        (text[..start].trim().to_string(), condition)
+    }
+}
+
+fn as_command(text: &str) -> Option<&str> {
+    text.strip_prefix("<<")?
+        .strip_suffix(">>")
+        .map(str::trim)
+        .filter(|command| !command.contains("<<"))
+}
+
+#[derive(Default)]
+struct Compiler {
+    paragraphs: Vec<Paragraph>,
+    node: String,
+    labels: usize,
+    jumps: Vec<(String, usize)>,
+    /// Whether the last paragraph is a plain line that options can be shown alongside
+    attachable: bool,
+}
+impl Compiler {
+    fn push(&mut self, paragraph: Paragraph) {
+        self.paragraphs.push(paragraph);
+        self.attachable = false;
+    }
+    fn label(&mut self) -> String {
+        self.labels += 1;
+        format!("{}#{}", self.node, self.labels)
+    }
+
+    fn compile(&mut self, statements: Vec<Statement>) {
+        statements
+            .into_iter()
+            .for_each(|statement| match statement {
+                Statement::Line {
+                    speaker,
+                    text,
+                    id,
+                    condition,
+                } => {
+                    let mut sentence = Sentence::new().with_text(text);
+                    if let Some(speaker) = speaker {
+                        sentence.set_speaker(speaker);
+                    }
+                    if let Some(id) = id {
+                        sentence.set_line_id(id);
+                    }
+                    let mut paragraph = Paragraph::new().with_sentences(vec![sentence]);
+
+                    let attachable = condition.is_none();
+                    if let Some(condition) = condition {
+                        paragraph.set_condition(condition);
+                    }
+                    self.push(paragraph);
+                    self.attachable = attachable;
+                }
+                Statement::Options(options) => {
+                    let end = self.label();
+                    let targets = options
+                        .iter()
+                        .map(|option| {
+                            if option.body.is_empty() {
+                                end.clone()
+                            } else {
+                                self.label()
+                            }
+                        })
+                        .collect::<Vec<_>>();
+
+                    let choice = Choice::new().with_options(
+                        options
+                            .iter()
+                            .zip(targets.iter())
+                            .map(|(option, target)| {
+                                let mut choice_option =
+                                    ChoiceOption::new(&option.text).with_jump(target);
+                                if let Some(condition) = option.condition.clone() {
+                                    choice_option.set_condition(condition);
+                                }
+                                choice_option
+                            })
+                            .collect(),
+                    );
+
+                    match self.paragraphs.last_mut().filter(|_| self.attachable) {
+                        Some(last) => {
+                            last.set_choice(choice);
+                            last.set_jump(&end);
+                            self.attachable = false;
+                        }
+                        None => self.push(Paragraph::new().with_choice(choice).with_jump(&end)),
+                    }
+
+                    options
+                        .into_iter()
+                        .zip(targets)
+                        .filter(|(option, _)| !option.body.is_empty())
+                        .for_each(|(option, target)| {
+                            self.push(Paragraph::new().with_name(target));
+                            self.compile(option.body);
+                            self.push(Paragraph::new().with_jump(&end));
+                        });
+
+                    self.push(Paragraph::new().with_name(end));
+                }
+                Statement::If(branches) => {
+                    let end = self.label();
+                    let targets = branches.iter().map(|_| self.label()).collect::<Vec<_>>();
+
+                    // Every test is checked one after the other before anything else can change the variables
+                    let has_else = branches.iter().any(|(condition, _)| condition.is_none());
+                    branches
+                        .iter()
+                        .zip(targets.iter())
+                        .for_each(|((condition, _), target)| {
+                            let mut test = Paragraph::new().with_jump(target);
+                            if let Some(condition) = condition.clone() {
+                                test.set_condition(condition);
+                            }
+                            self.push(test);
+                        });
+                    if !has_else {
+                        self.push(Paragraph::new().with_jump(&end));
+                    }
+
+                    branches
+                        .into_iter()
+                        .zip(targets)
+                        .for_each(|((_, body), target)| {
+                            self.push(Paragraph::new().with_name(target));
+                            self.compile(body);
+                            self.push(Paragraph::new().with_jump(&end));
+                        });
+
+                    self.push(Paragraph::new().with_name(end));
+                }
+                Statement::Jump(target, line) => {
+                    self.push(Paragraph::new().with_jump(&target));
+                    self.jumps.push((target, line));
+                }
+                Statement::Set(change) => self.push(Paragraph::new().push_variable_change(change)),
+                Statement::Command(command) => self.push(Paragraph::new().push_command(command)),
+                Statement::Wait(seconds) => {
+                    self.push(Paragraph::new().with_sentences(vec![Sentence::wait_for(seconds)]))
+                }
+                Statement::Stop => self.push(Paragraph::new().with_stop(true)),
+            });
+    }
+}